@@ -7,6 +7,7 @@
 
 use bevy::prelude::*;
 use leafwing_input_playback::{
+    frame_counting::FrameCount,
     input_capture::{InputCapturePlugin, InputModesCaptured},
     input_playback::{InputPlaybackPlugin, PlaybackStrategy},
     timestamped_input::TimestampedInputs,
@@ -52,7 +53,8 @@ fn toggle_capture_vs_playback(
                     // Play back all recorded inputs at the same rate they were input
                     timestamped_input.frame_range()
                 {
-                    PlaybackStrategy::FrameRangeOnce(start, end)
+                    // The end of a frame range is excluded, so extend it to include the final event
+                    PlaybackStrategy::FrameRangeOnce(start, end + FrameCount(1))
                 } else {
                     // Do not play back events if none were recorded
                     PlaybackStrategy::Paused
@@ -68,7 +70,7 @@ fn toggle_capture_vs_playback(
                 *playback_strategy = PlaybackStrategy::Paused;
 
                 // Reset all input data, starting a new recording
-                *timestamped_input = TimestampedInputs::default();
+                timestamped_input.clear();
 
                 info!("Now capturing input.");
                 InputStrategy::Capture
@@ -1,9 +1,9 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 
 use leafwing_input_playback::{
+    hotkeys::RecordingHotkeysPlugin,
     input_capture::{InputCapturePlugin, InputModesCaptured},
     input_playback::{InputPlaybackPlugin, PlaybackStrategy},
-    timestamped_input::TimestampedInputs,
 };
 
 fn main() {
@@ -19,18 +19,11 @@ fn main() {
         .add_startup_system(setup)
         .add_system(spawn_boxes)
         .add_system(decay_boxes)
-        // Toggle between playback and capture by pressing Space
-        .insert_resource(InputStrategy::Playback)
-        .add_system(toggle_capture_vs_playback)
+        // Record by pressing F9, play back by pressing F10 and stop by pressing F11
+        .add_plugin(RecordingHotkeysPlugin)
         .run()
 }
 
-#[derive(Resource, PartialEq)]
-enum InputStrategy {
-    Capture,
-    Playback,
-}
-
 fn setup(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
@@ -96,45 +89,3 @@ fn decay_boxes(mut query: Query<(Entity, &mut Transform), With<Box>>, mut comman
         }
     }
 }
-
-fn toggle_capture_vs_playback(
-    mut input_modes: ResMut<InputModesCaptured>,
-    mut playback_strategy: ResMut<PlaybackStrategy>,
-    keyboard_input: Res<Input<KeyCode>>,
-    mut timestamped_input: ResMut<TimestampedInputs>,
-    mut input_strategy: ResMut<InputStrategy>,
-) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        *input_strategy = match *input_strategy {
-            InputStrategy::Capture => {
-                // Disable input capture
-                *input_modes = InputModesCaptured::DISABLE_ALL;
-                // Enable input playback
-                *playback_strategy = if let Some((start, end)) =
-                    // Play back all recorded inputs at the same rate they were input
-                    timestamped_input.frame_range()
-                {
-                    PlaybackStrategy::FrameRangeOnce(start, end)
-                } else {
-                    // Do not play back events if none were recorded
-                    PlaybackStrategy::Paused
-                };
-
-                info!("Now playing back input.");
-                InputStrategy::Playback
-            }
-            InputStrategy::Playback => {
-                // Enable input capture
-                *input_modes = InputModesCaptured::ENABLE_ALL;
-                // Disable input playback
-                *playback_strategy = PlaybackStrategy::Paused;
-
-                // Reset all input data, starting a new recording
-                *timestamped_input = TimestampedInputs::default();
-
-                info!("Now capturing input.");
-                InputStrategy::Capture
-            }
-        };
-    }
-}
@@ -0,0 +1,157 @@
+//! Captures and plays back the [`ActionState`] of [leafwing-input-manager](leafwing_input_manager), rather than raw device input.
+//!
+//! Recording at the level of actions captures what the player intended to do, independent of the bindings that were used to do it.
+//! This module is only available when the `leafwing-input-manager` feature is enabled.
+
+use bevy::app::{App, CoreSet, Plugin};
+use bevy::ecs::prelude::*;
+use bevy::time::Time;
+use bevy::utils::Duration;
+use leafwing_input_manager::action_state::ActionState;
+use leafwing_input_manager::plugin::InputManagerSystem;
+use leafwing_input_manager::Actionlike;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+use crate::frame_counting::{frame_counter, FrameCount};
+
+/// Captures and plays back the [`ActionState<A>`] of entities, once per frame
+///
+/// The [`ActionState`] of the entity with a [`CaptureActionState`] component is recorded into the [`ActionStateRecording<A>`] resource
+/// at the end of every frame.
+/// Entities with a [`PlaybackActionState`] component have their [`ActionState`] overwritten from that recording
+/// after the [`InputManagerPlugin`](leafwing_input_manager::plugin::InputManagerPlugin) has updated it.
+pub struct ActionStatePlugin<A: Actionlike> {
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for ActionStatePlugin<A> {
+    fn default() -> Self {
+        ActionStatePlugin {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> Plugin for ActionStatePlugin<A> {
+    fn build(&self, app: &mut App) {
+        // Avoid double-adding frame_counter
+        if !app.world.contains_resource::<FrameCount>() {
+            app.init_resource::<FrameCount>()
+                .add_system(frame_counter.in_base_set(CoreSet::First));
+        }
+
+        app.init_resource::<ActionStateRecording<A>>()
+            .add_system(capture_action_state::<A>.in_base_set(CoreSet::Last))
+            .add_system(
+                playback_action_state::<A>
+                    .in_base_set(CoreSet::PreUpdate)
+                    .in_set(InputManagerSystem::ManualControl),
+            );
+    }
+}
+
+/// The [`ActionState<A>`] of an entity on a single frame, stored in an [`ActionStateRecording`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionStateSnapshot<A: Actionlike> {
+    /// The frame on which the snapshot was taken
+    pub frame: FrameCount,
+    /// The amount of time that had elapsed since the app began when the snapshot was taken
+    pub time_since_startup: Duration,
+    /// The recorded state of every action
+    pub action_state: ActionState<A>,
+}
+
+/// A resource that stores one [`ActionStateSnapshot`] for each captured frame, sorted by [`FrameCount`]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionStateRecording<A: Actionlike> {
+    /// The recorded snapshots, with the oldest first
+    pub snapshots: Vec<ActionStateSnapshot<A>>,
+}
+
+impl<A: Actionlike> Default for ActionStateRecording<A> {
+    fn default() -> Self {
+        ActionStateRecording {
+            snapshots: Vec::new(),
+        }
+    }
+}
+
+impl<A: Actionlike> ActionStateRecording<A> {
+    /// The most recent snapshot taken on or before `frame`, if any
+    pub fn snapshot_at(&self, frame: FrameCount) -> Option<&ActionStateSnapshot<A>> {
+        let index = self
+            .snapshots
+            .partition_point(|snapshot| snapshot.frame <= frame);
+        index.checked_sub(1).map(|index| &self.snapshots[index])
+    }
+}
+
+/// Records the [`ActionState`] of this entity into the [`ActionStateRecording`] every frame
+///
+/// Only a single entity should be captured for each action type.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CaptureActionState;
+
+/// Overwrites the [`ActionState`] of this entity from the [`ActionStateRecording`] every frame
+///
+/// Recorded frames between `start` (inclusive) and `end` (exclusive) are played back once, at the same rate they were captured:
+/// on the first frame of playback, the snapshot from `start` is used, then the snapshot from the frame after, and so on.
+/// Frames without a snapshot of their own reuse the most recent snapshot before them.
+/// Once the range has been played, this component is removed.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaybackActionState {
+    /// The first recorded frame to play back
+    pub start: FrameCount,
+    /// The recorded frame to stop playing back at, which is not itself played
+    pub end: FrameCount,
+    /// The number of frames played back so far
+    elapsed: u64,
+}
+
+impl PlaybackActionState {
+    /// Plays back the recorded frames from `start` up to (but not including) `end`.
+    pub fn new(start: FrameCount, end: FrameCount) -> Self {
+        PlaybackActionState {
+            start,
+            end,
+            elapsed: 0,
+        }
+    }
+}
+
+/// Records the [`ActionState`] of the entity with a [`CaptureActionState`] component into the [`ActionStateRecording`].
+pub fn capture_action_state<A: Actionlike>(
+    query: Query<&ActionState<A>, With<CaptureActionState>>,
+    mut recording: ResMut<ActionStateRecording<A>>,
+    frame_count: Res<FrameCount>,
+    time: Res<Time>,
+) {
+    for action_state in query.iter() {
+        recording.snapshots.push(ActionStateSnapshot {
+            frame: *frame_count,
+            time_since_startup: time.elapsed(),
+            action_state: action_state.clone(),
+        });
+    }
+}
+
+/// Overwrites the [`ActionState`] of each entity with a [`PlaybackActionState`] component from the [`ActionStateRecording`].
+pub fn playback_action_state<A: Actionlike>(
+    mut query: Query<(Entity, &mut ActionState<A>, &mut PlaybackActionState)>,
+    recording: Res<ActionStateRecording<A>>,
+    mut commands: Commands,
+) {
+    for (entity, mut action_state, mut playback) in query.iter_mut() {
+        let frame = FrameCount(playback.start.0 + playback.elapsed);
+        if frame >= playback.end {
+            commands.entity(entity).remove::<PlaybackActionState>();
+            continue;
+        }
+
+        if let Some(snapshot) = recording.snapshot_at(frame) {
+            *action_state = snapshot.action_state.clone();
+        }
+        playback.elapsed += 1;
+    }
+}
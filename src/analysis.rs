@@ -0,0 +1,904 @@
+//! Read-only analysis of recorded [`TimestampedInputs`].
+//!
+//! None of these methods move the playback cursor.
+
+use bevy::input::gamepad::{ButtonSettings, GamepadButton, GamepadEvent};
+use bevy::input::keyboard::{KeyCode, KeyboardInput};
+use bevy::input::mouse::MouseButton;
+use bevy::input::touch::TouchPhase;
+use bevy::input::ButtonState;
+use bevy::utils::{Duration, HashMap, HashSet};
+use bevy::window::CursorMoved;
+use std::fmt::Write;
+
+use crate::frame_counting::FrameCount;
+use crate::timestamped_input::{InputEvent, InputKind, TimestampedInputEvent, TimestampedInputs};
+
+/// Summary statistics about a recording, computed by [`TimestampedInputs::stats`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InputStats {
+    /// The total number of events recorded
+    pub total_events: usize,
+    /// The number of events recorded per second of recording
+    ///
+    /// This is 0 if the recording spans no time at all.
+    pub events_per_second: f32,
+    /// The number of actions (key presses, button presses and new touches) per minute of recording
+    ///
+    /// This is 0 if the recording spans no time at all.
+    pub actions_per_minute: f32,
+    /// The sum of all gaps between consecutive events that are longer than [`InputStats::IDLE_THRESHOLD`]
+    pub total_idle_time: Duration,
+    /// The longest gap between any two consecutive events
+    pub longest_idle_gap: Duration,
+    /// The number of events recorded for each kind of input
+    pub events_by_kind: HashMap<InputKind, usize>,
+    /// The number of times each key was pressed, including key repeats
+    pub key_presses: HashMap<KeyCode, usize>,
+}
+
+impl InputStats {
+    /// Gaps between events which are longer than this are counted as idle time.
+    pub const IDLE_THRESHOLD: Duration = Duration::from_secs(1);
+}
+
+/// The number of events recorded for each category of input device, computed by [`TimestampedInputs::counts`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputCounts {
+    /// Keyboard events
+    pub keyboard: usize,
+    /// Mouse button, mouse wheel, cursor and mouse motion events
+    pub mouse: usize,
+    /// Touch events
+    pub touch: usize,
+    /// Gamepad connection, button and axis events
+    pub gamepad: usize,
+    /// Window resize and focus events
+    pub window: usize,
+    /// `AppExit` events
+    pub app_exit: usize,
+}
+
+impl TimestampedInputs {
+    /// Counts the events recorded for each category of input device.
+    ///
+    /// This only reads through the recording once, without allocating,
+    /// and so is cheap enough to call every frame.
+    pub fn counts(&self) -> InputCounts {
+        let mut counts = InputCounts::default();
+        for event in self.events.iter() {
+            let count = match event.input_event.kind() {
+                InputKind::Keyboard => &mut counts.keyboard,
+                InputKind::MouseButton
+                | InputKind::MouseWheel
+                | InputKind::CursorMoved
+                | InputKind::MouseMotion => &mut counts.mouse,
+                InputKind::Touch => &mut counts.touch,
+                InputKind::Gamepad => &mut counts.gamepad,
+                InputKind::WindowResized | InputKind::WindowFocused => &mut counts.window,
+                InputKind::AppExit => &mut counts.app_exit,
+            };
+            *count += 1;
+        }
+        counts
+    }
+
+    /// Computes summary statistics about this recording, such as actions-per-minute, idle time and key press counts.
+    ///
+    /// Rates are computed over the time between the first and last recorded events.
+    pub fn stats(&self) -> InputStats {
+        let mut stats = InputStats {
+            total_events: self.events.len(),
+            ..Default::default()
+        };
+        let mut n_actions = 0;
+
+        for (i, event) in self.events.iter().enumerate() {
+            *stats
+                .events_by_kind
+                .entry(event.input_event.kind())
+                .or_default() += 1;
+
+            if is_action(&event.input_event) {
+                n_actions += 1;
+            }
+
+            if let InputEvent::Keyboard(KeyboardInput {
+                key_code: Some(key_code),
+                state: ButtonState::Pressed,
+                ..
+            }) = event.input_event
+            {
+                *stats.key_presses.entry(key_code).or_default() += 1;
+            }
+
+            if let Some(previous) = i.checked_sub(1).map(|j| &self.events[j]) {
+                let gap = event
+                    .time_since_startup
+                    .saturating_sub(previous.time_since_startup);
+                stats.longest_idle_gap = stats.longest_idle_gap.max(gap);
+                if gap > InputStats::IDLE_THRESHOLD {
+                    stats.total_idle_time += gap;
+                }
+            }
+        }
+
+        let seconds = self
+            .time_range()
+            .map_or(0.0, |(start, end)| (end - start).as_secs_f32());
+        if seconds > 0.0 {
+            stats.events_per_second = stats.total_events as f32 / seconds;
+            stats.actions_per_minute = n_actions as f32 / (seconds / 60.0);
+        }
+
+        stats
+    }
+
+    /// Renders the events in this recording as an SVG image, `width` by `height` pixels in size.
+    ///
+    /// Time runs from left to right, spanning the [`TimestampedInputs::time_range`] of the recording.
+    /// Each [`InputKind`] is drawn in its own horizontal lane, in the order of [`InputKind::ALL`],
+    /// with one color-coded marker per event.
+    pub fn render_timeline_svg(&self, width: u32, height: u32) -> String {
+        let lane_height = height as f32 / InputKind::ALL.len() as f32;
+        let mut svg = String::new();
+
+        // Writing to a `String` cannot fail
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        let _ = writeln!(
+            svg,
+            r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+        );
+
+        if let Some((start, end)) = self.time_range() {
+            let span = (end - start).as_secs_f32();
+            for event in &self.events {
+                let kind = event.input_event.kind();
+                let lane = InputKind::ALL.iter().position(|k| *k == kind).unwrap_or(0);
+                let progress = if span > 0.0 {
+                    (event.time_since_startup - start).as_secs_f32() / span
+                } else {
+                    0.0
+                };
+                let x = progress * width.saturating_sub(1) as f32;
+                let y = lane as f32 * lane_height;
+                let _ = writeln!(
+                    svg,
+                    r#"<rect class="event" x="{x:.2}" y="{y:.2}" width="1" height="{lane_height:.2}" fill="{}"><title>{kind:?} (frame {})</title></rect>"#,
+                    timeline_color(kind),
+                    event.frame.0,
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// An input that can be pressed and released, extracted from an [`InputEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ButtonlikeInput {
+    /// A keyboard key, identified by its [`KeyCode`]
+    Keyboard(KeyCode),
+    /// A mouse button
+    MouseButton(MouseButton),
+    /// A button on a specific gamepad
+    GamepadButton(GamepadButton),
+}
+
+impl ButtonlikeInput {
+    /// The button that was pressed or released by `input_event`, if any, and whether it was pressed.
+    ///
+    /// Keyboard events without a [`KeyCode`] are ignored.
+    /// Gamepad buttons are considered pressed and released using the thresholds of the default [`ButtonSettings`];
+    /// button events between those thresholds return [`None`].
+    pub fn from_event(input_event: &InputEvent) -> Option<(ButtonlikeInput, bool)> {
+        match input_event {
+            InputEvent::Keyboard(event) => event.key_code.map(|key_code| {
+                (
+                    ButtonlikeInput::Keyboard(key_code),
+                    event.state == ButtonState::Pressed,
+                )
+            }),
+            InputEvent::MouseButton(event) => Some((
+                ButtonlikeInput::MouseButton(event.button),
+                event.state == ButtonState::Pressed,
+            )),
+            InputEvent::Gamepad(GamepadEvent::Button(event)) => {
+                let button = ButtonlikeInput::GamepadButton(GamepadButton::new(
+                    event.gamepad,
+                    event.button_type,
+                ));
+                let settings = ButtonSettings::default();
+                if event.value >= settings.press_threshold() {
+                    Some((button, true))
+                } else if event.value <= settings.release_threshold() {
+                    Some((button, false))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A press or release without a matching counterpart, found by [`TimestampedInputs::validate_pairing`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnpairedInput {
+    /// The `input` was pressed on `frame`, but never released afterwards
+    NeverReleased {
+        /// The input that was left held
+        input: ButtonlikeInput,
+        /// The frame of the unmatched press
+        frame: FrameCount,
+    },
+    /// The `input` was released on `frame`, without being pressed beforehand
+    NeverPressed {
+        /// The input that was released
+        input: ButtonlikeInput,
+        /// The frame of the unmatched release
+        frame: FrameCount,
+    },
+}
+
+impl TimestampedInputs {
+    /// Checks that neither the [`FrameCount`] nor the time since startup ever decreases from one event to the next.
+    ///
+    /// Seeking and playback rely on this ordering, so call this after editing a recording by hand.
+    /// Returns the index of every event that is earlier than the event before it, in order;
+    /// use [`TimestampedInputs::sort_stable`] to fix the ordering.
+    pub fn validate(&self) -> Result<(), Vec<usize>> {
+        let out_of_order: Vec<usize> = self
+            .events
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| {
+                pair[1].frame < pair[0].frame
+                    || pair[1].time_since_startup < pair[0].time_since_startup
+            })
+            .map(|(index, _)| index + 1)
+            .collect();
+
+        if out_of_order.is_empty() {
+            Ok(())
+        } else {
+            Err(out_of_order)
+        }
+    }
+
+    /// Lists every key and button that is pressed but never released, or released without being pressed.
+    ///
+    /// Recordings which leave inputs held will leave those inputs stuck when played back.
+    /// Repeated presses of an input that is already held (such as from key repeat) are not reported.
+    ///
+    /// Unmatched releases are returned first, in the order they occur, followed by unmatched presses in the order they occur.
+    pub fn validate_pairing(&self) -> Vec<UnpairedInput> {
+        let mut unpaired = Vec::new();
+        // Held inputs are few, so a `Vec` keeps this simple and deterministic
+        let mut held: Vec<(ButtonlikeInput, FrameCount)> = Vec::new();
+
+        for event in &self.events {
+            let Some((input, pressed)) = ButtonlikeInput::from_event(&event.input_event) else {
+                continue;
+            };
+            let held_index = held.iter().position(|(held_input, _)| *held_input == input);
+
+            match (pressed, held_index) {
+                (true, None) => held.push((input, event.frame)),
+                (true, Some(_)) => (),
+                (false, Some(index)) => {
+                    held.remove(index);
+                }
+                (false, None) => unpaired.push(UnpairedInput::NeverPressed {
+                    input,
+                    frame: event.frame,
+                }),
+            }
+        }
+
+        unpaired.extend(
+            held.into_iter()
+                .map(|(input, frame)| UnpairedInput::NeverReleased { input, frame }),
+        );
+        unpaired
+    }
+}
+
+impl TimestampedInputs {
+    /// Every [`KeyCode`] that appears in a keyboard event of this recording, whether pressed or released.
+    ///
+    /// Keyboard events without a [`KeyCode`] are ignored.
+    pub fn keys_used(&self) -> HashSet<KeyCode> {
+        self.events
+            .iter()
+            .filter_map(|event| event.input_event.as_keyboard()?.key_code)
+            .collect()
+    }
+
+    /// Every [`MouseButton`] that appears in a mouse button event of this recording, whether pressed or released.
+    pub fn mouse_buttons_used(&self) -> HashSet<MouseButton> {
+        self.events
+            .iter()
+            .filter_map(|event| event.input_event.as_mouse_button())
+            .map(|event| event.button)
+            .collect()
+    }
+
+    /// Every [`GamepadButton`] that appears in a gamepad button event of this recording, whatever its value.
+    pub fn gamepad_buttons_used(&self) -> HashSet<GamepadButton> {
+        self.events
+            .iter()
+            .filter_map(|event| event.input_event.as_gamepad_button())
+            .map(|event| GamepadButton::new(event.gamepad, event.button_type))
+            .collect()
+    }
+}
+
+/// The keys and buttons held at a point in a recording, computed by [`TimestampedInputs::pressed_at`]
+pub type PressedSet = HashSet<ButtonlikeInput>;
+
+impl TimestampedInputs {
+    /// Reconstructs which keys and buttons are held once every event up to and including `frame` has been played.
+    ///
+    /// Inputs that were already held when the recording began are not known, and so are treated as not held:
+    /// releases without a matching press are ignored.
+    /// This only reads the recorded events, and does not affect the live [`Input`](bevy::input::Input) resources.
+    pub fn pressed_at(&self, frame: FrameCount) -> PressedSet {
+        let end = self.events.partition_point(|event| event.frame <= frame);
+        let mut pressed = PressedSet::default();
+
+        for event in &self.events[..end] {
+            match ButtonlikeInput::from_event(&event.input_event) {
+                Some((input, true)) => {
+                    pressed.insert(input);
+                }
+                Some((input, false)) => {
+                    pressed.remove(&input);
+                }
+                None => (),
+            }
+        }
+        pressed
+    }
+
+    /// The most recent [`CursorMoved`] event recorded before the event at `index`, if any.
+    ///
+    /// This is where the cursor was when that event was recorded, and is used to place the cursor for played back clicks.
+    /// If `index` is past the end of the recording, every event is searched.
+    pub fn cursor_moved_before(&self, index: usize) -> Option<CursorMoved> {
+        self.events[..index.min(self.events.len())]
+            .iter()
+            .rev()
+            .find_map(|other| match &other.input_event {
+                InputEvent::CursorMoved(cursor_moved) => Some(cursor_moved.clone()),
+                _ => None,
+            })
+    }
+}
+
+/// A divergence between two recordings, found by [`TimestampedInputs::diff`]
+///
+/// Indexes refer to positions in the `events` of each recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputDiff {
+    /// The event at `index` in the first recording has no counterpart in the second
+    Missing {
+        /// The index of the event in the first recording
+        index: usize,
+        /// The frame on which the event was recorded
+        frame: FrameCount,
+        /// The time since startup at which the event was recorded
+        time_since_startup: Duration,
+    },
+    /// The event at `index` in the second recording has no counterpart in the first
+    Extra {
+        /// The index of the event in the second recording
+        index: usize,
+        /// The frame on which the event was recorded
+        frame: FrameCount,
+        /// The time since startup at which the event was recorded
+        time_since_startup: Duration,
+    },
+    /// The same event appears in both recordings, but at times that differ by more than the tolerance
+    TimestampMismatch {
+        /// The index of the event in the first recording
+        index: usize,
+        /// The index of the event in the second recording
+        other_index: usize,
+        /// The time since startup at which the event was recorded in the first recording
+        time_since_startup: Duration,
+        /// The time since startup at which the event was recorded in the second recording
+        other_time_since_startup: Duration,
+    },
+}
+
+impl TimestampedInputs {
+    /// Compares this recording against `other`, listing every way in which they differ.
+    ///
+    /// Shorthand for [`TimestampedInputs::diff_within`] with no tolerance.
+    pub fn diff(&self, other: &TimestampedInputs) -> Vec<InputDiff> {
+        self.diff_within(other, Duration::ZERO)
+    }
+
+    /// Compares this recording against `other`, listing every way in which they differ.
+    ///
+    /// The comparison is order-aware: events are matched up so that as many as possible appear in the same order in both recordings,
+    /// and every unmatched event is reported as [`InputDiff::Missing`] or [`InputDiff::Extra`].
+    /// Matched events whose times since startup differ by more than `tolerance` are reported as [`InputDiff::TimestampMismatch`].
+    /// Event sources are ignored.
+    ///
+    /// Divergences are returned in the order they occur.
+    /// An empty list means that the recordings are equivalent.
+    pub fn diff_within(&self, other: &TimestampedInputs, tolerance: Duration) -> Vec<InputDiff> {
+        let (a, b) = (&self.events, &other.events);
+        let same = |i: usize, j: usize| a[i].input_event == b[j].input_event;
+
+        // Matching events at the start and end are paired up directly, so only the events in between need to be searched
+        let shorter = a.len().min(b.len());
+        let prefix = (0..shorter).take_while(|&i| same(i, i)).count();
+        let suffix = (0..shorter - prefix)
+            .take_while(|&k| same(a.len() - 1 - k, b.len() - 1 - k))
+            .count();
+        let (n, m) = (a.len() - prefix - suffix, b.len() - prefix - suffix);
+
+        // The events in between are matched up in linear space, so long divergent recordings can still be compared
+        let mut matches = Vec::new();
+        common_subsequence(
+            &a[prefix..prefix + n],
+            &b[prefix..prefix + m],
+            (prefix, prefix),
+            &mut matches,
+        );
+
+        let mut diffs = Vec::new();
+        let compare = |index: usize, other_index: usize, diffs: &mut Vec<InputDiff>| {
+            let time_since_startup = a[index].time_since_startup;
+            let other_time_since_startup = b[other_index].time_since_startup;
+            let difference = time_since_startup.max(other_time_since_startup)
+                - time_since_startup.min(other_time_since_startup);
+            if difference > tolerance {
+                diffs.push(InputDiff::TimestampMismatch {
+                    index,
+                    other_index,
+                    time_since_startup,
+                    other_time_since_startup,
+                });
+            }
+        };
+
+        for index in 0..prefix {
+            compare(index, index, &mut diffs);
+        }
+
+        // Between each pair of matched events, unmatched events of the first recording are reported before those of the second
+        let (mut i, mut j) = (prefix, prefix);
+        let end = (prefix + n, prefix + m);
+        for (next_i, next_j) in matches.into_iter().chain(std::iter::once(end)) {
+            for (index, event) in a.iter().enumerate().take(next_i).skip(i) {
+                diffs.push(InputDiff::Missing {
+                    index,
+                    frame: event.frame,
+                    time_since_startup: event.time_since_startup,
+                });
+            }
+            for (index, event) in b.iter().enumerate().take(next_j).skip(j) {
+                diffs.push(InputDiff::Extra {
+                    index,
+                    frame: event.frame,
+                    time_since_startup: event.time_since_startup,
+                });
+            }
+            if (next_i, next_j) != end {
+                compare(next_i, next_j, &mut diffs);
+            }
+            (i, j) = (next_i + 1, next_j + 1);
+        }
+
+        for k in 0..suffix {
+            compare(a.len() - suffix + k, b.len() - suffix + k, &mut diffs);
+        }
+
+        diffs
+    }
+}
+
+/// Finds a longest common subsequence of the events in `a` and `b` using Hirschberg's algorithm, which only needs linear space.
+///
+/// The indexes of each matched pair of events, offset by `offsets`, are pushed onto `matches` in order.
+fn common_subsequence(
+    a: &[TimestampedInputEvent],
+    b: &[TimestampedInputEvent],
+    offsets: (usize, usize),
+    matches: &mut Vec<(usize, usize)>,
+) {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+    if let [event] = a {
+        if let Some(j) = b
+            .iter()
+            .position(|other| other.input_event == event.input_event)
+        {
+            matches.push((offsets.0, offsets.1 + j));
+        }
+        return;
+    }
+
+    // Split `b` wherever the first half of `a` and the second half of `a` can share the most matches
+    let mid = a.len() / 2;
+    let forward = common_subsequence_lengths(&a[..mid], b, false);
+    let backward = common_subsequence_lengths(&a[mid..], b, true);
+    let split = (0..=b.len())
+        .max_by_key(|&k| (forward[k] + backward[b.len() - k], std::cmp::Reverse(k)))
+        .unwrap_or(0);
+
+    common_subsequence(&a[..mid], &b[..split], offsets, matches);
+    common_subsequence(
+        &a[mid..],
+        &b[split..],
+        (offsets.0 + mid, offsets.1 + split),
+        matches,
+    );
+}
+
+/// The length of the longest common subsequence of `a` and the first `j` events of `b`, for each `j` from 0 to `b.len()`
+///
+/// If `reverse` is set, both `a` and `b` are read back to front, so each length is for the last `j` events of `b` instead.
+fn common_subsequence_lengths<'a>(
+    a: &'a [TimestampedInputEvent],
+    b: &'a [TimestampedInputEvent],
+    reverse: bool,
+) -> Vec<u32> {
+    let at = |events: &'a [TimestampedInputEvent], index: usize| {
+        let index = if reverse {
+            events.len() - 1 - index
+        } else {
+            index
+        };
+        &events[index].input_event
+    };
+
+    let mut lengths = vec![0u32; b.len() + 1];
+    for i in 0..a.len() {
+        let event = at(a, i);
+        // The length for the previous event of `a` and the previous event of `b`
+        let mut diagonal = 0;
+        for j in 0..b.len() {
+            let above = lengths[j + 1];
+            lengths[j + 1] = if event == at(b, j) {
+                diagonal + 1
+            } else {
+                above.max(lengths[j])
+            };
+            diagonal = above;
+        }
+    }
+    lengths
+}
+
+/// The color used to draw each [`InputKind`] in [`TimestampedInputs::render_timeline_svg`]
+fn timeline_color(kind: InputKind) -> &'static str {
+    match kind {
+        InputKind::Keyboard => "#1f77b4",
+        InputKind::MouseButton => "#ff7f0e",
+        InputKind::MouseWheel => "#2ca02c",
+        InputKind::CursorMoved => "#9467bd",
+        InputKind::MouseMotion => "#e377c2",
+        InputKind::Touch => "#8c564b",
+        InputKind::Gamepad => "#d62728",
+        InputKind::WindowResized => "#bcbd22",
+        InputKind::WindowFocused => "#17becf",
+        InputKind::AppExit => "#7f7f7f",
+    }
+}
+
+/// Does this event represent a deliberate action by the user?
+fn is_action(input_event: &InputEvent) -> bool {
+    match input_event {
+        InputEvent::Keyboard(event) => event.state == ButtonState::Pressed,
+        InputEvent::MouseButton(event) => event.state == ButtonState::Pressed,
+        InputEvent::Touch(event) => event.phase == TouchPhase::Started,
+        InputEvent::Gamepad(GamepadEvent::Button(event)) => event.value > 0.5,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::mouse::{MouseButtonInput, MouseScrollUnit, MouseWheel};
+
+    const PRESS: InputEvent = InputEvent::Keyboard(KeyboardInput {
+        scan_code: 1,
+        key_code: Some(KeyCode::F),
+        state: ButtonState::Pressed,
+    });
+
+    const RELEASE: InputEvent = InputEvent::Keyboard(KeyboardInput {
+        scan_code: 1,
+        key_code: Some(KeyCode::F),
+        state: ButtonState::Released,
+    });
+
+    const SCROLL: InputEvent = InputEvent::MouseWheel(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 1.0,
+    });
+
+    #[test]
+    fn timeline_svg() {
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(0), Duration::from_secs(0), PRESS);
+        inputs.send(FrameCount(1), Duration::from_secs(1), SCROLL);
+        inputs.send(FrameCount(2), Duration::from_secs(2), RELEASE);
+
+        let svg = inputs.render_timeline_svg(201, 100);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches(r#"class="event""#).count(), 3);
+        // The final event is drawn at the right edge, in the keyboard lane
+        assert!(svg.contains(r#"x="200.00" y="0.00""#));
+        // Mouse wheel events have their own lane
+        assert!(svg.contains(r#"x="100.00" y="30.00""#));
+
+        let empty_svg = TimestampedInputs::default().render_timeline_svg(100, 70);
+        assert_eq!(empty_svg.matches(r#"class="event""#).count(), 0);
+    }
+
+    #[test]
+    fn validate_pairing() {
+        const CLICK: InputEvent = InputEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+        });
+        const UNCLICK: InputEvent = InputEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Released,
+        });
+
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(0), Duration::ZERO, UNCLICK);
+        inputs.send(FrameCount(1), Duration::ZERO, PRESS);
+        // Key repeat
+        inputs.send(FrameCount(2), Duration::ZERO, PRESS);
+        inputs.send(FrameCount(3), Duration::ZERO, RELEASE);
+        inputs.send(FrameCount(4), Duration::ZERO, CLICK);
+
+        assert_eq!(
+            inputs.validate_pairing(),
+            vec![
+                UnpairedInput::NeverPressed {
+                    input: ButtonlikeInput::MouseButton(MouseButton::Left),
+                    frame: FrameCount(0),
+                },
+                UnpairedInput::NeverReleased {
+                    input: ButtonlikeInput::MouseButton(MouseButton::Left),
+                    frame: FrameCount(4),
+                },
+            ]
+        );
+
+        let mut paired = TimestampedInputs::default();
+        paired.send(FrameCount(0), Duration::ZERO, PRESS);
+        paired.send(FrameCount(1), Duration::ZERO, RELEASE);
+        assert!(paired.validate_pairing().is_empty());
+    }
+
+    #[test]
+    fn buttons_used() {
+        use bevy::input::gamepad::{Gamepad, GamepadButtonChangedEvent, GamepadButtonType};
+        use bevy::input::mouse::MouseButtonInput;
+
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(0), Duration::ZERO, PRESS);
+        inputs.send(FrameCount(1), Duration::ZERO, RELEASE);
+        inputs.send(
+            FrameCount(1),
+            Duration::ZERO,
+            InputEvent::MouseButton(MouseButtonInput {
+                button: MouseButton::Right,
+                state: ButtonState::Released,
+            }),
+        );
+        inputs.send(
+            FrameCount(2),
+            Duration::ZERO,
+            InputEvent::Gamepad(GamepadEvent::Button(GamepadButtonChangedEvent::new(
+                Gamepad::new(1),
+                GamepadButtonType::South,
+                0.0,
+            ))),
+        );
+
+        assert_eq!(inputs.keys_used(), HashSet::from_iter([KeyCode::F]));
+        assert_eq!(
+            inputs.mouse_buttons_used(),
+            HashSet::from_iter([MouseButton::Right])
+        );
+        assert_eq!(
+            inputs.gamepad_buttons_used(),
+            HashSet::from_iter([GamepadButton::new(
+                Gamepad::new(1),
+                GamepadButtonType::South
+            )])
+        );
+        assert!(TimestampedInputs::default().keys_used().is_empty());
+    }
+
+    #[test]
+    fn validate() {
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(0), Duration::ZERO, PRESS);
+        inputs.send(FrameCount(1), Duration::from_secs(1), RELEASE);
+        assert_eq!(inputs.validate(), Ok(()));
+
+        // The frame goes backwards, then the time does
+        inputs.send(FrameCount(0), Duration::from_secs(2), PRESS);
+        inputs.send(FrameCount(2), Duration::from_secs(3), RELEASE);
+        inputs.send(FrameCount(3), Duration::from_secs(1), PRESS);
+        assert_eq!(inputs.validate(), Err(vec![2, 4]));
+
+        assert_eq!(TimestampedInputs::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn pressed_at() {
+        const CLICK: InputEvent = InputEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+        });
+
+        let mut inputs = TimestampedInputs::default();
+        // Released without being pressed during the recording
+        inputs.send(FrameCount(0), Duration::ZERO, RELEASE);
+        inputs.send(FrameCount(1), Duration::ZERO, PRESS);
+        inputs.send(FrameCount(2), Duration::ZERO, CLICK);
+        inputs.send(FrameCount(3), Duration::ZERO, RELEASE);
+
+        assert!(inputs.pressed_at(FrameCount(0)).is_empty());
+        assert_eq!(
+            inputs.pressed_at(FrameCount(2)),
+            [
+                ButtonlikeInput::Keyboard(KeyCode::F),
+                ButtonlikeInput::MouseButton(MouseButton::Left),
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert_eq!(
+            inputs.pressed_at(FrameCount(10)),
+            [ButtonlikeInput::MouseButton(MouseButton::Left)]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let mut golden = TimestampedInputs::default();
+        golden.send(FrameCount(0), Duration::from_millis(0), PRESS);
+        golden.send(FrameCount(1), Duration::from_millis(10), SCROLL);
+        golden.send(FrameCount(2), Duration::from_millis(20), RELEASE);
+        assert!(golden.diff(&golden).is_empty());
+
+        let mut replayed = TimestampedInputs::default();
+        replayed.send(FrameCount(0), Duration::from_millis(1), PRESS);
+        replayed.send(FrameCount(2), Duration::from_millis(20), RELEASE);
+        replayed.send(FrameCount(3), Duration::from_millis(30), SCROLL);
+
+        assert_eq!(
+            golden.diff(&replayed),
+            vec![
+                InputDiff::TimestampMismatch {
+                    index: 0,
+                    other_index: 0,
+                    time_since_startup: Duration::from_millis(0),
+                    other_time_since_startup: Duration::from_millis(1),
+                },
+                InputDiff::Missing {
+                    index: 1,
+                    frame: FrameCount(1),
+                    time_since_startup: Duration::from_millis(10),
+                },
+                InputDiff::Extra {
+                    index: 2,
+                    frame: FrameCount(3),
+                    time_since_startup: Duration::from_millis(30),
+                },
+            ]
+        );
+
+        // Small timing differences can be ignored
+        assert_eq!(
+            golden
+                .diff_within(&replayed, Duration::from_millis(1))
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn diff_long_recordings() {
+        let events = [PRESS, SCROLL, RELEASE];
+        let mut golden = TimestampedInputs::default();
+        let mut replayed = TimestampedInputs::default();
+        for n in 0..1000 {
+            let event = events[n as usize % events.len()].clone();
+            golden.send(FrameCount(n), Duration::ZERO, event.clone());
+            // Every tenth event is dropped in the middle of the recording
+            if !(100..900).contains(&n) || n % 10 != 0 {
+                replayed.send(FrameCount(n), Duration::ZERO, event);
+            }
+        }
+
+        let diffs = golden.diff(&replayed);
+        assert_eq!(diffs.len(), 80);
+        for (diff, n) in diffs.iter().zip((100..900).step_by(10)) {
+            assert_eq!(
+                *diff,
+                InputDiff::Missing {
+                    index: n,
+                    frame: FrameCount(n as u64),
+                    time_since_startup: Duration::ZERO,
+                }
+            );
+        }
+        assert_eq!(golden.diff(&golden), Vec::new());
+    }
+
+    #[test]
+    fn counts() {
+        let mut inputs = TimestampedInputs::default();
+        assert_eq!(inputs.counts(), InputCounts::default());
+
+        inputs.send(FrameCount(0), Duration::ZERO, PRESS);
+        inputs.send(FrameCount(1), Duration::ZERO, SCROLL);
+        inputs.send(FrameCount(2), Duration::ZERO, RELEASE);
+        inputs.send(FrameCount(3), Duration::ZERO, InputEvent::AppExit);
+        assert_eq!(
+            inputs.counts(),
+            InputCounts {
+                keyboard: 2,
+                mouse: 1,
+                app_exit: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn empty_stats() {
+        let stats = TimestampedInputs::default().stats();
+        assert_eq!(stats, InputStats::default());
+    }
+
+    #[test]
+    fn stats() {
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(0), Duration::from_secs(0), PRESS);
+        inputs.send(FrameCount(1), Duration::from_millis(500), RELEASE);
+        inputs.send(FrameCount(2), Duration::from_secs(10), SCROLL);
+        inputs.send(FrameCount(3), Duration::from_secs(20), PRESS);
+        inputs.send(FrameCount(4), Duration::from_secs(30), RELEASE);
+
+        let stats = inputs.stats();
+        // Two presses in half a minute
+        assert_eq!(stats.actions_per_minute, 4.0);
+        assert_eq!(stats.longest_idle_gap, Duration::from_secs(10));
+        // The first half-second gap is short enough to not count as idle
+        assert_eq!(stats.total_idle_time, Duration::from_millis(29_500));
+        assert_eq!(stats.events_by_kind[&InputKind::Keyboard], 4);
+        assert_eq!(stats.events_by_kind[&InputKind::MouseWheel], 1);
+        assert!(!stats.events_by_kind.contains_key(&InputKind::Gamepad));
+        assert_eq!(stats.total_events, 5);
+        assert_eq!(stats.events_per_second, 5.0 / 30.0);
+        assert_eq!(stats.key_presses[&KeyCode::F], 2);
+        assert_eq!(stats.key_presses.len(), 1);
+    }
+}
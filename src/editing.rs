@@ -0,0 +1,183 @@
+//! Safe mutation of [`TimestampedInputs`], even while they are being played back.
+//!
+//! Editing the `events` list directly can leave the playback `cursor` pointing at the wrong event,
+//! or past the end of the list.
+//! Use [`TimestampedInputs::edit`] to make changes through a [`RecordingEditor`], which keeps the cursor in step.
+
+use crate::timestamped_input::{TimestampedInputEvent, TimestampedInputs};
+
+impl TimestampedInputs {
+    /// Edits this recording through a [`RecordingEditor`], keeping the `cursor` valid.
+    ///
+    /// Events that have already been played back stay played back, and the next event to play stays next
+    /// (unless it was removed, in which case the event that followed it is played next).
+    /// Once `f` returns, the cursor is re-validated so that it never points past the end of the recording.
+    pub fn edit(&mut self, f: impl FnOnce(&mut RecordingEditor)) {
+        let mut editor = RecordingEditor { inputs: self };
+        f(&mut editor);
+
+        self.cursor = self.cursor.min(self.events.len());
+        self.invalidate_index();
+    }
+}
+
+/// A guarded handle for modifying a [`TimestampedInputs`], created by [`TimestampedInputs::edit`]
+///
+/// Every method adjusts the playback `cursor` to account for the events that were added or removed before it.
+#[derive(Debug)]
+pub struct RecordingEditor<'a> {
+    inputs: &'a mut TimestampedInputs,
+}
+
+impl RecordingEditor<'_> {
+    /// The recorded events, in their current order
+    pub fn events(&self) -> &[TimestampedInputEvent] {
+        &self.inputs.events
+    }
+
+    /// The index of the next event to be played back
+    pub fn cursor(&self) -> usize {
+        self.inputs.cursor
+    }
+
+    /// Returns a mutable reference to the event at `index`, if any.
+    ///
+    /// Changing an event in place never moves the cursor.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut TimestampedInputEvent> {
+        self.inputs.events.get_mut(index)
+    }
+
+    /// Appends an event to the end of the recording.
+    pub fn push(&mut self, event: TimestampedInputEvent) {
+        self.inputs.events.push(event);
+    }
+
+    /// Inserts an event at `index`, shifting all later events back.
+    ///
+    /// Events inserted before the cursor are treated as already played.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of events.
+    pub fn insert(&mut self, index: usize, event: TimestampedInputEvent) {
+        self.inputs.events.insert(index, event);
+        if index < self.inputs.cursor {
+            self.inputs.cursor += 1;
+        }
+    }
+
+    /// Removes and returns the event at `index`, shifting all later events forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> TimestampedInputEvent {
+        let event = self.inputs.events.remove(index);
+        if index < self.inputs.cursor {
+            self.inputs.cursor -= 1;
+        }
+        event
+    }
+
+    /// Keeps only the events for which `keep` returns `true`, preserving their order.
+    pub fn retain(&mut self, mut keep: impl FnMut(&TimestampedInputEvent) -> bool) {
+        let cursor = self.inputs.cursor;
+        let mut new_cursor = cursor;
+        let mut index = 0;
+
+        self.inputs.events.retain(|event| {
+            let kept = keep(event);
+            if !kept && index < cursor {
+                new_cursor -= 1;
+            }
+            index += 1;
+            kept
+        });
+
+        self.inputs.cursor = new_cursor;
+    }
+
+    /// Removes every event, resetting the cursor to the start.
+    pub fn clear(&mut self) {
+        self.inputs.events.clear();
+        self.inputs.cursor = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_counting::FrameCount;
+    use crate::timestamped_input::InputEvent;
+    use bevy::input::mouse::{MouseButton, MouseButtonInput};
+    use bevy::input::ButtonState;
+    use bevy::utils::Duration;
+
+    fn click(frame: u64) -> TimestampedInputEvent {
+        TimestampedInputEvent::new(
+            FrameCount(frame),
+            Duration::from_secs(frame),
+            InputEvent::MouseButton(MouseButtonInput {
+                button: MouseButton::Left,
+                state: ButtonState::Pressed,
+            }),
+        )
+    }
+
+    fn recording_at_cursor(cursor: usize) -> TimestampedInputs {
+        let mut inputs = TimestampedInputs::default();
+        inputs.events = (0..5).map(click).collect();
+        inputs.cursor = cursor;
+        inputs
+    }
+
+    #[test]
+    fn insert_and_remove_preserve_next_event() {
+        let mut inputs = recording_at_cursor(2);
+
+        inputs.edit(|editor| {
+            editor.insert(0, click(10));
+            assert_eq!(editor.cursor(), 3);
+            // Inserting after the cursor does not move it
+            editor.insert(4, click(11));
+            assert_eq!(editor.cursor(), 3);
+        });
+        assert_eq!(inputs.current_framecount(), Some(FrameCount(2)));
+
+        inputs.edit(|editor| {
+            editor.remove(0);
+            editor.remove(0);
+        });
+        assert_eq!(inputs.cursor, 1);
+        assert_eq!(inputs.current_framecount(), Some(FrameCount(2)));
+
+        // Removing the next event makes the one after it next
+        inputs.edit(|editor| {
+            editor.remove(1);
+        });
+        assert_eq!(inputs.current_framecount(), Some(FrameCount(11)));
+    }
+
+    #[test]
+    fn retain_preserves_next_event() {
+        let mut inputs = recording_at_cursor(3);
+
+        inputs.edit(|editor| editor.retain(|event| ![1, 3].contains(&event.frame.0)));
+        assert_eq!(inputs.len(), 3);
+        assert_eq!(inputs.cursor, 2);
+        assert_eq!(inputs.current_framecount(), Some(FrameCount(4)));
+    }
+
+    #[test]
+    fn cursor_revalidated_after_edit() {
+        let mut inputs = recording_at_cursor(5);
+
+        inputs.edit(|editor| editor.retain(|_| false));
+        assert!(inputs.is_empty());
+        assert_eq!(inputs.cursor, 0);
+
+        let mut inputs = recording_at_cursor(4);
+        inputs.edit(|editor| editor.clear());
+        assert_eq!(inputs.cursor, 0);
+    }
+}
@@ -0,0 +1,106 @@
+//! Keyboard shortcuts for starting and stopping input capture and playback.
+//!
+//! Add the [`RecordingHotkeysPlugin`] alongside the [`InputCapturePlugin`](crate::input_capture::InputCapturePlugin)
+//! and [`InputPlaybackPlugin`](crate::input_playback::InputPlaybackPlugin) to control both from the keyboard.
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::prelude::*;
+use bevy::input::keyboard::KeyCode;
+use bevy::input::Input;
+use bevy::log::info;
+
+use crate::frame_counting::FrameCount;
+use crate::input_capture::InputModesCaptured;
+use crate::input_playback::PlaybackStrategy;
+use crate::timestamped_input::TimestampedInputs;
+
+/// Binds the keys in the [`RecordingHotkeys`] resource to starting and stopping input capture and playback
+///
+/// Pressing a hotkey changes the [`InputModesCaptured`] and [`PlaybackStrategy`] resources.
+/// The hotkeys themselves are never captured, so they do not end up in the played back input.
+///
+/// Requires the [`InputCapturePlugin`](crate::input_capture::InputCapturePlugin)
+/// and [`InputPlaybackPlugin`](crate::input_playback::InputPlaybackPlugin).
+pub struct RecordingHotkeysPlugin;
+
+impl Plugin for RecordingHotkeysPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecordingHotkeys>()
+            .add_system(toggle_recording_with_hotkeys);
+    }
+}
+
+/// The keys used by the [`RecordingHotkeysPlugin`], configured as a resource.
+///
+/// While this resource exists, [`capture_input`](crate::input_capture::capture_input) does not record these keys.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RecordingHotkeys {
+    /// Starts a fresh recording, discarding the current [`TimestampedInputs`]
+    ///
+    /// Defaults to [`KeyCode::F9`].
+    pub record: KeyCode,
+    /// Stops capturing input, and plays back the current recording once
+    ///
+    /// Defaults to [`KeyCode::F10`].
+    pub play: KeyCode,
+    /// Stops both capture and playback
+    ///
+    /// Defaults to [`KeyCode::F11`].
+    pub stop: KeyCode,
+}
+
+impl Default for RecordingHotkeys {
+    fn default() -> Self {
+        RecordingHotkeys {
+            record: KeyCode::F9,
+            play: KeyCode::F10,
+            stop: KeyCode::F11,
+        }
+    }
+}
+
+impl RecordingHotkeys {
+    /// Is the keyboard event with the provided `key_code` one of these hotkeys?
+    pub fn binds(&self, key_code: Option<KeyCode>) -> bool {
+        key_code.map_or(false, |key_code| {
+            [self.record, self.play, self.stop].contains(&key_code)
+        })
+    }
+}
+
+/// Starts and stops input capture and playback when the [`RecordingHotkeys`] are pressed.
+///
+/// If several hotkeys are pressed on the same frame, stopping takes priority over playing, which takes priority over recording.
+pub fn toggle_recording_with_hotkeys(
+    hotkeys: Res<RecordingHotkeys>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut input_modes: ResMut<InputModesCaptured>,
+    mut playback_strategy: ResMut<PlaybackStrategy>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+) {
+    if keyboard_input.just_pressed(hotkeys.stop) {
+        *input_modes = InputModesCaptured::DISABLE_ALL;
+        *playback_strategy = PlaybackStrategy::Paused;
+
+        info!("Stopped capturing and playing back input.");
+    } else if keyboard_input.just_pressed(hotkeys.play) {
+        *input_modes = InputModesCaptured::DISABLE_ALL;
+        timestamped_input.reset_cursor();
+        *playback_strategy = if let Some((start, end)) = timestamped_input.frame_range() {
+            // The end of a frame range is excluded, so extend it to include the final event
+            PlaybackStrategy::FrameRangeOnce(start, end + FrameCount(1))
+        } else {
+            // Do not play back events if none were recorded
+            PlaybackStrategy::Paused
+        };
+
+        info!("Now playing back input.");
+    } else if keyboard_input.just_pressed(hotkeys.record) {
+        *input_modes = InputModesCaptured::ENABLE_ALL;
+        *playback_strategy = PlaybackStrategy::Paused;
+        // Reset all input data, starting a new recording
+        timestamped_input.clear();
+
+        info!("Now capturing input.");
+    }
+}
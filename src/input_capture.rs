@@ -3,19 +3,22 @@
 //! These are unified into a single [`TimestampedInputs`](crate::timestamped_input::TimestampedInputs) resource, which can be played back.
 
 use bevy::app::{App, AppExit, CoreSet, Plugin};
-use bevy::ecs::prelude::*;
-use bevy::input::gamepad::GamepadEvent;
-use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::{MouseButtonInput, MouseWheel};
+use bevy::ecs::{prelude::*, system::SystemParam};
+use bevy::input::gamepad::{Gamepad, GamepadEvent};
+use bevy::input::keyboard::{KeyCode, KeyboardInput};
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseWheel};
+use bevy::input::touch::TouchInput;
+use bevy::input::{ButtonState, InputSystem};
+use bevy::math::Vec2;
 use bevy::time::Time;
-use bevy::window::CursorMoved;
-use ron::ser::PrettyConfig;
+use bevy::utils::HashSet;
+use bevy::window::{CursorMoved, PrimaryWindow, Window, WindowFocused, WindowResized};
+use smol_str::SmolStr;
 
 use crate::frame_counting::{frame_counter, FrameCount};
-use crate::serde::PlaybackFilePath;
-use crate::timestamped_input::TimestampedInputs;
-use std::fs::OpenOptions;
-use std::io::Write;
+use crate::hotkeys::RecordingHotkeys;
+use crate::serde::{save_recording, PlaybackFilePath, SaveRecording};
+use crate::timestamped_input::{TimestampedInputs, OS_SOURCE};
 
 /// Captures user inputs from the assorted raw `Event` types
 ///
@@ -23,6 +26,7 @@ use std::io::Write;
 /// Which input modes (mouse, keyboard, etc) are captured is controlled via the [`InputModesCaptured`] resource.
 ///
 /// Input is serialized into the path stored in the [`PlaybackFilePath`] resource, if any.
+/// Send a [`SaveRecording`] event to save the captured input at any other time.
 pub struct InputCapturePlugin;
 
 impl Plugin for InputCapturePlugin {
@@ -35,53 +39,143 @@ impl Plugin for InputCapturePlugin {
 
         app.init_resource::<TimestampedInputs>()
             .init_resource::<InputModesCaptured>()
+            .init_resource::<AxisPrecision>()
+            .init_resource::<KeyFilter>()
+            .init_resource::<GamepadFilter>()
+            .init_resource::<CaptureCapacityHint>()
+            .init_resource::<CaptureStartDelay>()
+            .init_resource::<CaptureFrameLimit>()
+            .init_resource::<CapturePassthrough>()
+            .init_resource::<CaptureOnlyTransitions>()
+            .init_resource::<CaptureRealTime>()
             .init_resource::<PlaybackFilePath>()
+            .add_event::<SaveRecording>()
+            .add_event::<CaptureStarted>()
+            .add_event::<CaptureStopped>()
+            // Guaranteed by the base sets already, but stated so that it is never lost
+            .configure_set(CaptureSet.after(InputSystem))
+            .add_system(
+                enforce_capture_frame_limit
+                    .in_set(CaptureSet)
+                    .in_base_set(CoreSet::Last)
+                    .before(send_capture_lifecycle_events),
+            )
+            .add_system(
+                send_capture_lifecycle_events
+                    .in_set(CaptureSet)
+                    .in_base_set(CoreSet::Last)
+                    .before(capture_input),
+            )
+            .add_system(
+                reserve_capture_capacity
+                    .in_set(CaptureSet)
+                    .in_base_set(CoreSet::Last)
+                    .before(capture_input),
+            )
+            .add_system(
+                record_capture_resolution
+                    .in_set(CaptureSet)
+                    .in_base_set(CoreSet::Last)
+                    .before(capture_input),
+            )
             .add_system(
                 // Capture any mocked input as well
-                capture_input.in_base_set(CoreSet::Last),
+                capture_input.in_set(CaptureSet).in_base_set(CoreSet::Last),
+            )
+            .add_system(
+                consume_captured_input
+                    .in_set(CaptureSet)
+                    .in_base_set(CoreSet::Last)
+                    .after(capture_input),
             )
             .add_system(
                 serialize_captured_input_on_exit
+                    .in_set(CaptureSet)
+                    .in_base_set(CoreSet::Last)
+                    .after(capture_input),
+            )
+            .add_system(
+                save_recording
+                    .in_set(CaptureSet)
                     .in_base_set(CoreSet::Last)
                     .after(capture_input),
             );
     }
 }
 
+/// The systems that capture input, added by the [`InputCapturePlugin`]
+///
+/// These systems run in [`CoreSet::Last`], and so always run after Bevy's [`InputSystem`](bevy::input::InputSystem)
+/// has processed the frame's device events in [`CoreSet::PreUpdate`].
+/// Every event sent during the frame, whether by a device or by your own systems, is present when capture reads it.
+/// Order your own systems in [`CoreSet::Last`] before this set to have the events they send recorded on the same frame.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CaptureSet;
+
 /// The input mechanisms captured via the [`InputCapturePlugin`], configured as a resource.
 ///
 /// By default, all supported input modes will be captured.
 #[derive(Resource, Debug, PartialEq, Eq, Clone)]
 pub struct InputModesCaptured {
-    /// Mouse buttons and mouse wheel inputs
+    /// Mouse button inputs
     pub mouse_buttons: bool,
-    /// Moving the mouse
+    /// Mouse wheel inputs
+    ///
+    /// Captures both the scroll unit (lines or pixels) and the scrolled amounts.
+    pub mouse_wheel: bool,
+    /// Moving the mouse cursor
+    ///
+    /// Captures [`CursorMoved`] events, including the window and the cursor's position relative to it.
     pub mouse_motion: bool,
+    /// Relative [`MouseMotion`] deltas, as used for mouse look
+    ///
+    /// Unlike `mouse_motion`, these are reported even when the cursor is locked or hidden.
+    pub mouse_deltas: bool,
     /// Keyboard inputs
     ///
     /// Captures both keycode and scan code data.
     pub keyboard: bool,
+    /// Touchscreen inputs
+    ///
+    /// Captures the phase, position, force and finger id of each touch.
+    pub touch: bool,
     /// Gamepad inputs
     ///
-    /// Captures gamepad connections, button presses and axis values
+    /// Captures gamepad connections, button presses and axis values, as read from [`GamepadEvent`].
+    /// `bevy_gilrs` applies the [`GamepadSettings`](bevy::input::gamepad::GamepadSettings) deadzones and change thresholds
+    /// before sending those events, so the recorded values are the filtered values that the app itself sees.
+    /// Changes that are filtered out entirely are never sent, and so cannot be captured;
+    /// to record values closer to the raw hardware values, loosen the axis settings while capturing.
     pub gamepad: bool,
+    /// Window events
+    ///
+    /// Captures [`WindowResized`] and [`WindowFocused`] events, including the window they were sent for.
+    pub window: bool,
 }
 
 impl InputModesCaptured {
     /// Disables all input capturing
     pub const DISABLE_ALL: InputModesCaptured = InputModesCaptured {
         mouse_buttons: false,
+        mouse_wheel: false,
         mouse_motion: false,
+        mouse_deltas: false,
         keyboard: false,
+        touch: false,
         gamepad: false,
+        window: false,
     };
 
     /// Captures all supported input modes
     pub const ENABLE_ALL: InputModesCaptured = InputModesCaptured {
         mouse_buttons: true,
+        mouse_wheel: true,
         mouse_motion: true,
+        mouse_deltas: true,
         keyboard: true,
+        touch: true,
         gamepad: true,
+        window: true,
     };
 }
 
@@ -91,36 +185,432 @@ impl Default for InputModesCaptured {
     }
 }
 
+/// Which keys are recorded by [`capture_input`], configured as a resource.
+///
+/// This is only consulted when [`InputModesCaptured::keyboard`] is enabled.
+/// Events for filtered-out keys are not recorded at all.
+///
+/// By default, every key is recorded.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Default)]
+pub enum KeyFilter {
+    /// Every key is recorded
+    #[default]
+    All,
+    /// Only the listed keys are recorded
+    ///
+    /// Keyboard events without a [`KeyCode`] are never recorded.
+    Allow(HashSet<KeyCode>),
+    /// Every key except the listed keys is recorded
+    ///
+    /// Keyboard events without a [`KeyCode`] are always recorded.
+    Deny(HashSet<KeyCode>),
+}
+
+impl KeyFilter {
+    /// Should the keyboard event with the provided `key_code` be recorded?
+    pub fn allows(&self, key_code: Option<KeyCode>) -> bool {
+        match (self, key_code) {
+            (KeyFilter::All, _) => true,
+            (KeyFilter::Allow(keys), Some(key_code)) => keys.contains(&key_code),
+            (KeyFilter::Allow(_), None) => false,
+            (KeyFilter::Deny(keys), Some(key_code)) => !keys.contains(&key_code),
+            (KeyFilter::Deny(_), None) => true,
+        }
+    }
+}
+
+/// Which gamepads are recorded by [`capture_input`], configured as a resource.
+///
+/// This is only consulted when [`InputModesCaptured::gamepad`] is enabled.
+/// Events from other gamepads (including their connection events) are not recorded at all.
+///
+/// By default, every gamepad is recorded.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum GamepadFilter {
+    /// Events from every gamepad are recorded
+    #[default]
+    All,
+    /// Only events from the provided gamepad are recorded
+    Only(Gamepad),
+}
+
+impl GamepadFilter {
+    /// Should events from the provided `gamepad` be recorded?
+    pub fn allows(&self, gamepad: Gamepad) -> bool {
+        match self {
+            GamepadFilter::All => true,
+            GamepadFilter::Only(only) => *only == gamepad,
+        }
+    }
+}
+
+/// The number of events that the [`TimestampedInputs`] should have room for, configured as a resource.
+///
+/// The [`InputCapturePlugin`] reserves this much space up front, so long capture sessions do not reallocate
+/// (and hitch) as the recording grows. For example, a 30 minute session at 60 frames per second
+/// with a few events per frame needs a hint of a few hundred thousand events.
+/// Space is reserved again whenever the [`TimestampedInputs`] resource is replaced.
+///
+/// By default, this is 0, and so no space is reserved.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CaptureCapacityHint(pub usize);
+
+/// Reserves space in the [`TimestampedInputs`] for the number of events given by the [`CaptureCapacityHint`].
+pub fn reserve_capture_capacity(
+    hint: Res<CaptureCapacityHint>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+) {
+    // Only mutably dereference when reallocating, to avoid triggering change detection every frame
+    if timestamped_input.events.capacity() < hint.0 {
+        let additional = hint.0 - timestamped_input.len();
+        timestamped_input.events.reserve(additional);
+    }
+}
+
+/// Stores the size of the primary window in the [`RecordingMetadata::resolution`](crate::timestamped_input::RecordingMetadata::resolution)
+/// on the first frame that input is captured.
+///
+/// A resolution that has already been set is never overwritten; clear the [`TimestampedInputs`] to record it afresh.
+pub fn record_capture_resolution(
+    input_modes_captured: Res<InputModesCaptured>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+) {
+    if *input_modes_captured == InputModesCaptured::DISABLE_ALL
+        || timestamped_input.metadata.resolution.is_some()
+    {
+        return;
+    }
+
+    if let Ok(window) = primary_window.get_single() {
+        timestamped_input.metadata.resolution = Some(Vec2::new(window.width(), window.height()));
+    }
+}
+
+/// Records only keyboard events that change whether a key is held, if set to `true`, configured as a resource.
+///
+/// Holding a key down makes the operating system repeat its press event.
+/// When this is enabled, those repeated presses are discarded, so only the initial press and the final release are recorded.
+/// Keys are told apart by their scan code, and played back recordings still hold each key for as long as it was held.
+/// Which keys are held is tracked from every keyboard event, including those that are not captured,
+/// such as while [`InputModesCaptured::keyboard`] is disabled.
+///
+/// Defaults to `false`, so every keyboard event is recorded.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CaptureOnlyTransitions(pub bool);
+
+/// Timestamps captured events with the real time elapsed since startup, if set to `true`, configured as a resource.
+///
+/// By default, events are timestamped with [`Time::elapsed`], which only advances once per frame,
+/// and can be paused or set manually (for example, with a [`TimeUpdateStrategy`](bevy::time::TimeUpdateStrategy)).
+/// Enabling this instead measures the wall-clock time since the app started, as of when [`capture_input`] runs,
+/// which is useful for profiling real input latency.
+///
+/// Time-based playback strategies compare recorded times against the app's [`Time`],
+/// so recordings made with this enabled can drift during playback if the two clocks disagree.
+/// Frame-based strategies are unaffected.
+///
+/// Defaults to `false`.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CaptureRealTime(pub bool);
+
+/// The first frame on which device input is recorded by [`capture_input`], configured as a resource.
+///
+/// Apps often send spurious input and window events as they start up;
+/// all such events sent before this frame are discarded.
+/// [`AppExit`] events are always recorded.
+///
+/// By default, this is frame 0, and so input is recorded from the very first frame.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CaptureStartDelay(pub FrameCount);
+
+/// The last frame on which input is recorded by [`capture_input`], configured as a resource.
+///
+/// Once the current frame exceeds this limit, [`enforce_capture_frame_limit`] disables capture
+/// by setting [`InputModesCaptured`] to [`InputModesCaptured::DISABLE_ALL`], which sends a [`CaptureStopped`] event.
+/// This bounds the size of recordings made by headless runs that never stop capture themselves.
+///
+/// By default, this is [`None`], and so input is captured indefinitely.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CaptureFrameLimit(pub Option<FrameCount>);
+
+/// Sent by [`send_capture_lifecycle_events`] whenever input capture starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureStarted {
+    /// The first frame on which input was captured
+    pub frame: FrameCount,
+}
+
+/// Sent by [`send_capture_lifecycle_events`] whenever input capture stops
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureStopped {
+    /// The frame on which capture stopped, which was not itself recorded
+    pub frame: FrameCount,
+}
+
+/// Are events left in place for other systems to read after being recorded by [`capture_input`]? Configured as a resource.
+///
+/// [`capture_input`] reads events with an [`EventReader`], which never removes them from the [`Events`] queue.
+/// When this is `true`, capture is transparent: every other system sees exactly the same events as it would without capture.
+///
+/// When this is `false`, [`consume_captured_input`] clears the queues of every captured input mode once they have been recorded.
+/// As capture runs in [`CoreSet::Last`], systems earlier in the frame still read that frame's events.
+/// Bevy's events are double-buffered, and are normally readable until the end of the following frame;
+/// clearing hides them from systems that run after capture, including those in the following frame.
+///
+/// Defaults to `true`.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CapturePassthrough(pub bool);
+
+impl Default for CapturePassthrough {
+    fn default() -> Self {
+        CapturePassthrough(true)
+    }
+}
+
+/// Clears the event queues of every captured input mode, if [`CapturePassthrough`] is `false`.
+///
+/// [`AppExit`] events are never cleared.
+#[allow(clippy::too_many_arguments)]
+pub fn consume_captured_input(
+    passthrough: Res<CapturePassthrough>,
+    input_modes_captured: Res<InputModesCaptured>,
+    mut keyboard_input: ResMut<Events<KeyboardInput>>,
+    mut mouse_button_input: ResMut<Events<MouseButtonInput>>,
+    mut mouse_wheel: ResMut<Events<MouseWheel>>,
+    mut cursor_moved: ResMut<Events<CursorMoved>>,
+    mut mouse_motion: ResMut<Events<MouseMotion>>,
+    mut touch_input: ResMut<Events<TouchInput>>,
+    mut gamepad: ResMut<Events<GamepadEvent>>,
+    mut window_resized: ResMut<Events<WindowResized>>,
+    mut window_focused: ResMut<Events<WindowFocused>>,
+) {
+    if passthrough.0 {
+        return;
+    }
+
+    if input_modes_captured.keyboard {
+        keyboard_input.clear();
+    }
+    if input_modes_captured.mouse_buttons {
+        mouse_button_input.clear();
+    }
+    if input_modes_captured.mouse_wheel {
+        mouse_wheel.clear();
+    }
+    if input_modes_captured.mouse_motion {
+        cursor_moved.clear();
+    }
+    if input_modes_captured.mouse_deltas {
+        mouse_motion.clear();
+    }
+    if input_modes_captured.touch {
+        touch_input.clear();
+    }
+    if input_modes_captured.gamepad {
+        gamepad.clear();
+    }
+    if input_modes_captured.window {
+        window_resized.clear();
+        window_focused.clear();
+    }
+}
+
+/// Sends [`CaptureStarted`] and [`CaptureStopped`] events when [`InputModesCaptured`] changes
+/// between [`InputModesCaptured::DISABLE_ALL`] and any other value.
+///
+/// Capture is treated as stopped before the app starts,
+/// so [`CaptureStarted`] is sent on the first frame if any input modes are enabled.
+pub fn send_capture_lifecycle_events(
+    input_modes_captured: Res<InputModesCaptured>,
+    frame_count: Res<FrameCount>,
+    mut was_capturing: Local<bool>,
+    mut capture_started: EventWriter<CaptureStarted>,
+    mut capture_stopped: EventWriter<CaptureStopped>,
+) {
+    let capturing = *input_modes_captured != InputModesCaptured::DISABLE_ALL;
+    let frame = *frame_count;
+
+    match (*was_capturing, capturing) {
+        (false, true) => capture_started.send(CaptureStarted { frame }),
+        (true, false) => capture_stopped.send(CaptureStopped { frame }),
+        _ => (),
+    }
+    *was_capturing = capturing;
+}
+
+/// Disables input capture once the [`CaptureFrameLimit`] has been exceeded.
+pub fn enforce_capture_frame_limit(
+    frame_limit: Res<CaptureFrameLimit>,
+    frame_count: Res<FrameCount>,
+    mut input_modes_captured: ResMut<InputModesCaptured>,
+) {
+    let Some(limit) = frame_limit.0 else {
+        return;
+    };
+
+    if *frame_count > limit && *input_modes_captured != InputModesCaptured::DISABLE_ALL {
+        *input_modes_captured = InputModesCaptured::DISABLE_ALL;
+    }
+}
+
+/// The precision with which gamepad axis values are recorded by [`capture_input`], configured as a resource.
+///
+/// Reducing the precision rounds axis values to a fixed number of steps,
+/// which makes serialized recordings of gamepad-heavy input substantially smaller at the cost of fidelity.
+/// Played back axis values are the rounded values that were recorded.
+///
+/// By default, axis values are recorded at full precision.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum AxisPrecision {
+    /// Axis values are recorded exactly
+    #[default]
+    Full,
+    /// Axis values are rounded to the provided number of bits, including the sign bit.
+    ///
+    /// With `Bits(8)`, values are rounded to the nearest multiple of 1/128.
+    /// The number of bits is clamped to `1..=24`, the range that an [`f32`] can represent exactly.
+    Bits(u8),
+}
+
+impl AxisPrecision {
+    /// Rounds the axis `value` to this precision.
+    ///
+    /// The result is clamped to `[-1.0, 1.0]` when the precision is reduced.
+    pub fn quantize(&self, value: f32) -> f32 {
+        match *self {
+            AxisPrecision::Full => value,
+            AxisPrecision::Bits(bits) => {
+                let steps = (1u32 << (bits.clamp(1, 24) - 1)) as f32;
+                ((value * steps).round() / steps).clamp(-1.0, 1.0)
+            }
+        }
+    }
+}
+
+/// The resources that control which input is recorded by [`capture_input`]
+#[derive(SystemParam)]
+#[allow(missing_docs)]
+pub struct CaptureSettings<'w> {
+    pub input_modes_captured: Res<'w, InputModesCaptured>,
+    pub axis_precision: Res<'w, AxisPrecision>,
+    pub key_filter: Res<'w, KeyFilter>,
+    pub gamepad_filter: Res<'w, GamepadFilter>,
+    pub start_delay: Res<'w, CaptureStartDelay>,
+    pub only_transitions: Res<'w, CaptureOnlyTransitions>,
+    pub real_time: Res<'w, CaptureRealTime>,
+    pub hotkeys: Option<Res<'w, RecordingHotkeys>>,
+}
+
 /// Captures input from the [`bevy::window`] and [`bevy::input`] event streams.
 ///
 /// The input modes can be controlled via the [`InputModesCaptured`] resource.
+///
+/// All events read in the same frame are recorded with the same [`FrameCount`], and so are played back together.
+/// Within a frame, keyboard events are always recorded before any other input events,
+/// so modifier keys (like Shift) are applied before the clicks that they modify.
+/// If the [`RecordingHotkeys`] resource exists, its keys are never recorded.
+/// No device input is recorded before the frame given by the [`CaptureStartDelay`].
+/// Repeated key presses are discarded when [`CaptureOnlyTransitions`] is enabled,
+/// and events are timestamped with the wall-clock time when [`CaptureRealTime`] is enabled.
 #[allow(clippy::too_many_arguments)]
 pub fn capture_input(
     mut mouse_button_events: EventReader<MouseButtonInput>,
     mut mouse_wheel_events: EventReader<MouseWheel>,
     mut cursor_moved_events: EventReader<CursorMoved>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
     mut keyboard_events: EventReader<KeyboardInput>,
+    mut touch_events: EventReader<TouchInput>,
     mut gamepad_events: EventReader<GamepadEvent>,
+    mut window_resized_events: EventReader<WindowResized>,
+    mut window_focused_events: EventReader<WindowFocused>,
     mut app_exit_events: EventReader<AppExit>,
     mut timestamped_input: ResMut<TimestampedInputs>,
-    input_modes_captured: Res<InputModesCaptured>,
+    settings: CaptureSettings,
     frame_count: Res<FrameCount>,
     time: Res<Time>,
+    mut held_keys: Local<HashSet<u32>>,
 ) {
-    let time_since_startup = time.elapsed();
+    let time_since_startup = if settings.real_time.0 {
+        time.startup().elapsed()
+    } else {
+        time.elapsed()
+    };
     let frame = *frame_count;
+    let n_events_before = timestamped_input.len();
+
+    // Every key event updates which keys are held, even if it is not captured,
+    // so that a release that was never recorded does not make the next press look like a repeat
+    let mut keyboard_events: Vec<_> = keyboard_events
+        .iter()
+        .filter(|event| {
+            let is_repeat = match event.state {
+                ButtonState::Pressed => !held_keys.insert(event.scan_code),
+                // Releases always end a hold, even if the press was never seen
+                ButtonState::Released => {
+                    held_keys.remove(&event.scan_code);
+                    false
+                }
+            };
+            !(is_repeat && settings.only_transitions.0)
+        })
+        .cloned()
+        .collect();
+
+    // Events sent before capture starts are discarded, rather than recorded once it does
+    if frame < settings.start_delay.0 {
+        mouse_button_events.clear();
+        mouse_wheel_events.clear();
+        cursor_moved_events.clear();
+        mouse_motion_events.clear();
+        keyboard_events.clear();
+        touch_events.clear();
+        gamepad_events.clear();
+        window_resized_events.clear();
+        window_focused_events.clear();
+    }
 
     // BLOCKED: these events are arbitrarily ordered within a frame,
     // but we have no way to access their order from winit.
     // See https://github.com/bevyengine/bevy/issues/5984
 
-    if input_modes_captured.mouse_buttons {
+    // Keyboard events are recorded first, so that modifier keys are always
+    // seen before the mouse and gamepad events that they modify.
+    if settings.input_modes_captured.keyboard {
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            keyboard_events
+                .into_iter()
+                .filter(|event| settings.key_filter.allows(event.key_code))
+                .filter(|event| {
+                    !settings
+                        .hotkeys
+                        .as_ref()
+                        .map_or(false, |h| h.binds(event.key_code))
+                }),
+        );
+    }
+
+    // Cursor movement is recorded before mouse buttons,
+    // so that clicks are played back where the cursor was moved to on the same frame.
+    if settings.input_modes_captured.mouse_motion {
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            cursor_moved_events.iter().cloned(),
+        );
+    }
+
+    if settings.input_modes_captured.mouse_buttons {
         timestamped_input.send_multiple(
             frame,
             time_since_startup,
             mouse_button_events.iter().cloned(),
         );
+    }
 
+    if settings.input_modes_captured.mouse_wheel {
         timestamped_input.send_multiple(
             frame,
             time_since_startup,
@@ -128,25 +618,65 @@ pub fn capture_input(
         );
     }
 
-    if input_modes_captured.mouse_motion {
+    if settings.input_modes_captured.mouse_deltas {
         timestamped_input.send_multiple(
             frame,
             time_since_startup,
-            cursor_moved_events.iter().cloned(),
+            mouse_motion_events.iter().cloned(),
         );
     }
 
-    if input_modes_captured.keyboard {
-        timestamped_input.send_multiple(frame, time_since_startup, keyboard_events.iter().cloned());
+    if settings.input_modes_captured.touch {
+        timestamped_input.send_multiple(frame, time_since_startup, touch_events.iter().cloned());
     }
 
-    if input_modes_captured.gamepad {
-        timestamped_input.send_multiple(frame, time_since_startup, gamepad_events.iter().cloned());
+    if settings.input_modes_captured.gamepad {
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            gamepad_events
+                .iter()
+                .filter(|event| settings.gamepad_filter.allows(event_gamepad(event)))
+                .cloned()
+                .map(|event| match event {
+                    GamepadEvent::Axis(mut axis_event) => {
+                        axis_event.value = settings.axis_precision.quantize(axis_event.value);
+                        GamepadEvent::Axis(axis_event)
+                    }
+                    event => event,
+                }),
+        );
+    }
+
+    if settings.input_modes_captured.window {
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            window_resized_events.iter().cloned(),
+        );
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            window_focused_events.iter().cloned(),
+        );
+    }
+
+    for event in timestamped_input.events[n_events_before..].iter_mut() {
+        event.source = Some(SmolStr::new_inline(OS_SOURCE));
     }
 
     timestamped_input.send_multiple(frame, time_since_startup, app_exit_events.iter().cloned())
 }
 
+/// The gamepad that sent the provided `event`
+fn event_gamepad(event: &GamepadEvent) -> Gamepad {
+    match event {
+        GamepadEvent::Connection(event) => event.gamepad,
+        GamepadEvent::Button(event) => event.gamepad,
+        GamepadEvent::Axis(event) => event.gamepad,
+    }
+}
+
 /// Serializes captured input to the path given in the [`PlaybackFilePath`] resource.
 ///
 /// This data is only serialized once when [`AppExit`] is sent.
@@ -167,17 +697,8 @@ pub fn serialize_timestamped_inputs(
     playback_file: &PlaybackFilePath,
 ) {
     if let Some(file_path) = playback_file.path() {
-        let mut file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .open(file_path)
-            .expect("Could not open file.");
-        write!(
-            file,
-            "{}",
-            ron::ser::to_string_pretty(timestamped_inputs, PrettyConfig::default())
-                .expect("Could not convert captured input to a string.")
-        )
-        .expect("Could not write string to file.");
+        timestamped_inputs
+            .write_ron(file_path)
+            .expect("Could not write captured input to file.");
     }
 }
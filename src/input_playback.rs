@@ -2,27 +2,31 @@
 //!
 //! These are played back by emulating assorted Bevy input events.
 
-use bevy::app::{App, AppExit, CoreSet, Plugin};
+use bevy::app::{App, AppExit, CoreSchedule, CoreSet, IntoSystemAppConfig, Plugin};
 use bevy::ecs::{prelude::*, system::SystemParam};
-use bevy::input::gamepad::GamepadEvent;
+use bevy::input::gamepad::{Gamepad, GamepadButton, GamepadEvent};
 use bevy::input::{
-    keyboard::KeyboardInput,
-    mouse::{MouseButtonInput, MouseWheel},
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    Input,
 };
 use bevy::log::warn;
-use bevy::time::Time;
-use bevy::utils::Duration;
-use bevy::window::{CursorMoved, Window};
-use ron::de::from_reader;
-use std::fs::File;
+use bevy::math::Vec2;
+use bevy::time::{Time, TimeSystem};
+use bevy::utils::{Duration, HashMap};
+use bevy::window::{CursorMoved, PrimaryWindow, Window, WindowFocused, WindowResized};
+use std::ops::RangeInclusive;
 
+use crate::analysis::ButtonlikeInput;
 use crate::frame_counting::{frame_counter, FrameCount};
+use crate::library::RecordingLibrary;
 use crate::serde::PlaybackFilePath;
-use crate::timestamped_input::{TimestampedInputEvent, TimestampedInputs};
+use crate::timestamped_input::{InputEvent, TimestampedInputEvent, TimestampedInputs};
 
 /// Reads from the [`TimestampedInputs`] event stream to determine which events to play back.
 ///
-/// Events are played back during [`CoreStage::First`] to accurately mimic the behavior of native `winit`-based inputs.
+/// Events are played back during [`CoreSet::First`], in the [`PlaybackSet`], to accurately mimic the behavior of native `winit`-based inputs.
 /// Which events are played back are controlled via the [`PlaybackStrategy`] resource.
 ///  
 /// Input is deserialized on app startup from the path stored in the [`PlaybackFilePath`] resource, if any.
@@ -36,23 +40,104 @@ impl Plugin for InputPlaybackPlugin {
                 .add_system(frame_counter.in_base_set(CoreSet::First));
         }
 
-        app.init_resource::<TimestampedInputs>()
-            .init_resource::<PlaybackProgress>()
-            .init_resource::<PlaybackStrategy>()
-            .init_resource::<PlaybackFilePath>()
-            .add_startup_system(deserialize_timestamped_inputs)
-            .add_system(
-                playback_timestamped_input
-                    .after(frame_counter)
-                    .in_base_set(CoreSet::First),
-            );
+        add_playback_resources(app);
+        app.add_system(
+            suppress_live_input
+                .after(frame_counter)
+                .before(playback_timestamped_input)
+                .in_set(PlaybackSet)
+                .in_base_set(CoreSet::First),
+        )
+        .add_system(
+            playback_timestamped_input
+                .after(frame_counter)
+                .after(TimeSystem)
+                .in_set(PlaybackSet)
+                .in_base_set(CoreSet::First),
+        );
     }
 }
 
+/// The systems that play back input, added by the [`InputPlaybackPlugin`] and [`FixedPlaybackPlugin`]
+///
+/// With the [`InputPlaybackPlugin`], these systems run in [`CoreSet::First`],
+/// and so always run before Bevy's [`InputSystem`](bevy::input::InputSystem) updates resources such as [`Input`](bevy::input::Input)
+/// in [`CoreSet::PreUpdate`].
+/// Played back input is therefore visible to every system in [`CoreSet::PreUpdate`] and later on the frame it is played.
+/// Order your own systems in [`CoreSet::First`] after this set to read played back events as soon as they are sent.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlaybackSet;
+
+/// Plays back [`TimestampedInputs`] on the fixed timestep, rather than once per frame
+///
+/// This is an alternative to the [`InputPlaybackPlugin`]: add one or the other, not both.
+/// The [`playback_timestamped_input`] system runs in [`CoreSchedule::FixedUpdate`],
+/// so recorded events are released in step with simulation ticks, however quickly frames are rendered.
+///
+/// In this mode, the [`FrameCount`] resource counts fixed ticks rather than frames.
+/// Each tick advances it by one, so a recording captured on the fixed timestep replays tick for tick,
+/// and the frame-based strategies (such as [`PlaybackStrategy::FrameCount`]) should be preferred.
+/// Add this plugin before the [`InputCapturePlugin`](crate::input_capture::InputCapturePlugin),
+/// so that capture shares the same tick-based [`FrameCount`].
+///
+/// Played back events are sent during the fixed timestep, so systems in [`CoreSchedule::FixedUpdate`] can read them immediately.
+/// Resources such as [`Input`](bevy::input::Input) are only updated by Bevy during the next frame's [`CoreSet::PreUpdate`].
+pub struct FixedPlaybackPlugin;
+
+impl Plugin for FixedPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        // Avoid double-adding frame_counter
+        if !app.world.contains_resource::<FrameCount>() {
+            app.init_resource::<FrameCount>()
+                .add_system(frame_counter.in_schedule(CoreSchedule::FixedUpdate));
+        }
+
+        add_playback_resources(app);
+        app.add_system(
+            suppress_live_input
+                .after(frame_counter)
+                .before(playback_timestamped_input)
+                .in_set(PlaybackSet)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        )
+        .add_system(
+            playback_timestamped_input
+                .after(frame_counter)
+                .in_set(PlaybackSet)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
+    }
+}
+
+/// The resources and events shared by the [`InputPlaybackPlugin`] and [`FixedPlaybackPlugin`]
+fn add_playback_resources(app: &mut App) {
+    app.init_resource::<TimestampedInputs>()
+        .init_resource::<PlaybackProgress>()
+        .init_resource::<PlaybackStrategy>()
+        .init_resource::<PlaybackMixMode>()
+        .init_resource::<PlaybackSpeed>()
+        .init_resource::<PlaybackPauseWhenUnfocused>()
+        .init_resource::<PlaybackMaxEventsPerUpdate>()
+        .init_resource::<PlaybackScaleCursor>()
+        .init_resource::<PlaybackWriteInputState>()
+        .init_resource::<GamepadRemap>()
+        .init_resource::<PlaybackControl>()
+        .init_resource::<PlaybackWindowRemap>()
+        .init_resource::<PlaybackModes>()
+        .init_resource::<PlaybackJitter>()
+        .init_resource::<PlaybackRng>()
+        .init_resource::<RecordingLibrary>()
+        .add_event::<PlaybackComplete>()
+        .add_event::<LoopCompleted>()
+        .add_event::<StepPlayback>()
+        .init_resource::<PlaybackFilePath>()
+        .add_startup_system(deserialize_timestamped_inputs);
+}
+
 /// Controls the approach used for playing back recorded inputs
 ///
 /// [`PlaybackStrategy::Time`] is the default strategy.
-#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Default)]
 pub enum PlaybackStrategy {
     /// Plays events up to (but not past) the current [`Time`].
     ///
@@ -62,11 +147,20 @@ pub enum PlaybackStrategy {
     /// Plays events up to (but not past) the current [`FrameCount`].
     ///
     /// This strategy is faster, as you can turn off any frame rate limiting mechanism.
+    ///
+    /// Each update, every event from the `cursor` onwards whose frame is less than or equal to the current [`FrameCount`] is played.
+    /// This means that when this strategy is enabled partway through an app's run,
+    /// events recorded on the frame where playback begins are played on that frame,
+    /// alongside any earlier events that have not yet been read.
+    /// Because the `cursor` is advanced past every event that is played,
+    /// each event is played exactly once: events are never repeated on the following frame.
     FrameCount,
     /// Plays events between the first and second [`Duration`] once, measured in time since app startup.
     ///
     /// The events are played back at the same rate they were captured.
-    /// This range includes events sent at the start of the range, but not the end.
+    /// This range includes events sent at the start of the range, but not the end,
+    /// and events are compared against their recorded [`Duration`], not their [`FrameCount`].
+    /// Once the whole range has been played, this strategy swaps itself to [`PlaybackStrategy::Paused`].
     TimeRangeOnce(Duration, Duration),
     /// Plays events between the first and second [`Duration`] indefinitely, measured in time since app startup.
     ///
@@ -85,12 +179,448 @@ pub enum PlaybackStrategy {
     /// This range includes events sent at the start of the range, but not the end.
     /// There will always be one frame between the end of the previous loop and the start of the next.
     FrameRangeLoop(FrameCount, FrameCount),
+    /// Plays events between the first and second [`FrameCount`] the provided number of times.
+    ///
+    /// Each loop behaves like [`PlaybackStrategy::FrameRangeLoop`].
+    /// The number stored in this strategy is the number of loops left to play, and counts down as each loop finishes.
+    /// Once it reaches zero, this strategy swaps itself to [`PlaybackStrategy::Paused`].
+    FrameRangeLoopN(FrameCount, FrameCount, u32),
+    /// Plays each range of [`FrameCount`]s in the list once, in order.
+    ///
+    /// Each range is played back like [`PlaybackStrategy::FrameRangeOnce`]:
+    /// at the same rate it was captured, including events sent at the start of the range, but not the end.
+    /// There will always be one frame between the end of one range and the start of the next,
+    /// and ranges that are empty (or whose end is before their start) are skipped.
+    /// Once every range has been played, this strategy swaps itself to [`PlaybackStrategy::Paused`].
+    Playlist(Vec<(FrameCount, FrameCount)>),
+    /// Plays each [`PlaybackSegment`] in the list in order, repeating each one as many times as it requests.
+    ///
+    /// Each pass through a segment is played back like [`PlaybackStrategy::FrameRangeLoop`],
+    /// with one frame between the end of one pass (or segment) and the start of the next.
+    /// Segments that are empty, or that are played zero times, are skipped,
+    /// and a segment that loops forever is never left.
+    /// Once every segment has been played, this strategy swaps itself to [`PlaybackStrategy::Paused`].
+    Composite(Vec<PlaybackSegment>),
+    /// Plays back every event once, at the same real-time cadence that it was captured.
+    ///
+    /// Unlike [`PlaybackStrategy::Time`], which compares events against the app's own clock,
+    /// this measures time from the moment this strategy begins,
+    /// so events are released once their recorded time since startup has elapsed since playback began.
+    /// If an update takes a long time, every overdue event is released at once.
+    /// Once all events have been played, this strategy swaps itself to [`PlaybackStrategy::Paused`].
+    RealTime,
+    /// Plays back every event once, stretched or compressed so that playback takes the provided [`Duration`].
+    ///
+    /// The speed of playback is chosen so that the span between the first and last recorded events fits the target duration;
+    /// relative timings between events are preserved.
+    /// Once all events have been played, this strategy swaps itself to [`PlaybackStrategy::Paused`].
+    FitDuration(Duration),
+    /// Plays back exactly one event for each [`StepPlayback`] event sent, and otherwise stays paused.
+    ///
+    /// This is useful for debugging, to advance through a recording one input at a time.
+    /// Stepping past the end of the recording does nothing.
+    Step,
     /// Does not playback any events.
     ///
     /// This is useful for interactive use cases, to temporarily disable sending events.
     Paused,
 }
 
+/// A range of frames within a [`PlaybackStrategy::Composite`], and how many times to play it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaybackSegment {
+    /// The first frame of the range
+    pub start: FrameCount,
+    /// The frame at which the range ends, which is not itself played
+    pub end: FrameCount,
+    /// The number of times to play the range, or [`None`] to loop it forever
+    pub loops: Option<u32>,
+}
+
+impl PlaybackSegment {
+    /// Plays the frames from `start` up to (but not including) `end` once.
+    pub fn once(start: FrameCount, end: FrameCount) -> Self {
+        PlaybackSegment {
+            start,
+            end,
+            loops: Some(1),
+        }
+    }
+
+    /// Plays the frames from `start` up to (but not including) `end` forever.
+    pub fn forever(start: FrameCount, end: FrameCount) -> Self {
+        PlaybackSegment {
+            start,
+            end,
+            loops: None,
+        }
+    }
+
+    /// Does this segment have nothing to play?
+    fn is_empty(&self) -> bool {
+        self.start >= self.end || self.loops == Some(0)
+    }
+}
+
+/// Pauses and resumes playback, remembering which [`PlaybackStrategy`] was active, configured as a resource.
+///
+/// Neither method moves the `cursor` of the [`TimestampedInputs`], so resumed playback continues from where it left off.
+/// The [`PlaybackStrategy`] is swapped without triggering change detection,
+/// so a resumed [`PlaybackStrategy::Playlist`] or [`PlaybackStrategy::Composite`] continues from the same segment,
+/// and [`LoopCompleted`] events keep counting from the same iteration.
+/// Call these from a system with access to both this resource and the [`PlaybackStrategy`] resource, such as a play/pause button.
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub struct PlaybackControl {
+    stashed: Option<PlaybackStrategy>,
+}
+
+impl PlaybackControl {
+    /// Swaps the `strategy` to [`PlaybackStrategy::Paused`], remembering the strategy that was active.
+    ///
+    /// Does nothing if playback is already paused.
+    pub fn pause(&mut self, strategy: &mut impl DetectChangesMut<Inner = PlaybackStrategy>) {
+        let strategy = strategy.bypass_change_detection();
+        if *strategy != PlaybackStrategy::Paused {
+            self.stashed = Some(std::mem::replace(strategy, PlaybackStrategy::Paused));
+        }
+    }
+
+    /// Restores the strategy that was active when [`PlaybackControl::pause`] was last called.
+    ///
+    /// Does nothing if playback was not paused through this resource.
+    pub fn resume(&mut self, strategy: &mut impl DetectChangesMut<Inner = PlaybackStrategy>) {
+        if let Some(stashed) = self.stashed.take() {
+            *strategy.bypass_change_detection() = stashed;
+        }
+    }
+
+    /// Was playback paused by [`PlaybackControl::pause`], and not yet resumed?
+    pub fn is_paused(&self) -> bool {
+        self.stashed.is_some()
+    }
+}
+
+/// Controls how played back inputs are combined with live inputs from the user.
+///
+/// [`PlaybackMixMode::Additive`] is the default.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaybackMixMode {
+    /// Live input events are discarded while playback is active.
+    ///
+    /// Playback is considered active whenever the [`PlaybackStrategy`] is not [`PlaybackStrategy::Paused`].
+    /// `AppExit` events are never discarded.
+    ///
+    /// Live events are discarded by [`suppress_live_input`], which only sees events that were sent before playback.
+    /// This includes all `winit` input, but not live gamepad input:
+    /// `bevy_gilrs` sends gamepad events later in the frame, during [`CoreSet::PreUpdate`], so they still reach the game.
+    /// Remove the `GilrsPlugin` while overriding to discard live gamepad input too.
+    Override,
+    /// Played back events are sent alongside any live input events.
+    ///
+    /// Live events are sent before played back events in each frame,
+    /// so when both sources press or release the same button in the same frame,
+    /// the played back event is processed last and determines whether the button is held.
+    /// Both presses and releases are still reported by `just_pressed` and `just_released` for that frame.
+    #[default]
+    Additive,
+}
+
+/// Sent by [`playback_timestamped_input`] whenever a pass of playback finishes.
+///
+/// For [`PlaybackStrategy::Time`], [`PlaybackStrategy::FrameCount`] and [`PlaybackStrategy::Step`],
+/// this is sent on the update where the final event is played.
+/// For the range strategies, this is sent once the range has been exhausted:
+/// the `Once` variants send this a single time before pausing, while the `Loop` variants send this each time they loop.
+/// [`PlaybackStrategy::FrameRangeLoopN`] only sends this once, after its final loop.
+/// For [`PlaybackStrategy::Playlist`] and [`PlaybackStrategy::Composite`], this is sent once, after the final range has been played.
+/// For [`PlaybackStrategy::RealTime`] and [`PlaybackStrategy::FitDuration`], this is sent once all events have been played.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaybackComplete {
+    /// The strategy that was active when playback finished
+    pub strategy: PlaybackStrategy,
+}
+
+/// Sent by [`playback_timestamped_input`] each time [`PlaybackStrategy::FrameRangeLoop`], [`PlaybackStrategy::TimeRangeLoop`]
+/// or [`PlaybackStrategy::FrameRangeLoopN`] returns to the start of its range,
+/// or a segment of a [`PlaybackStrategy::Composite`] is repeated.
+///
+/// For the unbounded loops, this is sent alongside the corresponding [`PlaybackComplete`] event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopCompleted {
+    /// The number of loops completed so far, starting at 1 for the first completed loop
+    ///
+    /// This count is reset whenever the [`PlaybackStrategy`] resource is changed.
+    pub iteration: u32,
+}
+
+/// Send this event to play back the next recorded event while using [`PlaybackStrategy::Step`].
+///
+/// Each [`StepPlayback`] event advances the `cursor` by one.
+/// These events are ignored by all other strategies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StepPlayback;
+
+/// Scales how quickly recorded time elapses during playback, relative to real time.
+///
+/// At `2.0`, each update plays back twice as much recorded [`Duration`]; at `0.5`, half as much.
+/// Values at or below zero (or that are not finite) pause playback, without resetting its progress.
+///
+/// This affects [`PlaybackStrategy::RealTime`], [`PlaybackStrategy::TimeRangeOnce`], [`PlaybackStrategy::TimeRangeLoop`]
+/// and [`PlaybackStrategy::FitDuration`].
+/// [`PlaybackStrategy::Time`] always follows the app's own clock, and the frame-based strategies are unaffected.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackSpeed(pub f32);
+
+impl Default for PlaybackSpeed {
+    fn default() -> Self {
+        PlaybackSpeed(1.0)
+    }
+}
+
+impl PlaybackSpeed {
+    /// Converts a `delta` of real time into the amount of recorded time that should be played back.
+    pub fn scale(&self, delta: Duration) -> Duration {
+        if self.0.is_finite() && self.0 > 0.0 {
+            delta.mul_f64(self.0 as f64)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Pauses playback while the primary window is unfocused, if set to `true`, configured as a resource.
+///
+/// While paused, no events are played back and the progress of the range, real time and duration-fitting strategies is held,
+/// so those strategies resume exactly where they left off once the window is focused again.
+/// [`PlaybackStrategy::Time`] and [`PlaybackStrategy::FrameCount`] follow the app's own clocks,
+/// and so play back any events that were skipped as soon as the window is refocused.
+/// If there is no primary window, playback is never paused.
+///
+/// Defaults to `false`, so playback continues regardless of focus.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackPauseWhenUnfocused(pub bool);
+
+/// Scales played back cursor and touch positions to the current window size, if set to `true`, configured as a resource.
+///
+/// Positions are scaled by the ratio between the size of the window they are sent to
+/// and the [`RecordingMetadata::resolution`](crate::timestamped_input::RecordingMetadata::resolution) of the recording,
+/// so a click recorded in the middle of a 1920x1080 window lands in the middle of a 1280x720 window.
+/// Touches are scaled to the primary window.
+/// Recordings without a resolution are played back unscaled.
+///
+/// Defaults to `false`, so positions are played back exactly as recorded.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackScaleCursor(pub bool);
+
+/// Presses and releases played back keys and buttons directly in Bevy's [`Input`] resources, if set to `true`, configured as a resource.
+///
+/// Playback normally only sends events, which Bevy's [`InputPlugin`](bevy::input::InputPlugin) systems convert
+/// into the [`Input<KeyCode>`], [`Input<MouseButton>`] and [`Input<GamepadButton>`] resources.
+/// Enable this if those systems are not running, so that code that only reads the [`Input`] resources still sees the played back input.
+/// Events are sent either way.
+/// Gamepad buttons are pressed and released using the thresholds of the default [`ButtonSettings`](bevy::input::gamepad::ButtonSettings),
+/// and [`Input`] resources that do not exist are skipped.
+///
+/// Bevy's systems are also responsible for clearing the `just_pressed` and `just_released` state each frame,
+/// and for tracking the state of live input, so do not enable this alongside them.
+///
+/// Defaults to `false`.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackWriteInputState(pub bool);
+
+/// The maximum number of recorded events sent in a single update by [`playback_timestamped_input`], configured as a resource.
+///
+/// After a stall, a single update may be due to play back a large backlog of events.
+/// Any events past this limit are deferred, and played back (subject to the same limit) on the following updates.
+/// Only events that are actually played back count towards the limit: events of disabled [`PlaybackModes`] are skipped freely.
+/// Range strategies only finish or loop once every deferred event in their range has been played back.
+/// The limit is always at least 1.
+///
+/// Defaults to [`usize::MAX`], so every due event is played back immediately.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaybackMaxEventsPerUpdate(pub usize);
+
+impl Default for PlaybackMaxEventsPerUpdate {
+    fn default() -> Self {
+        PlaybackMaxEventsPerUpdate(usize::MAX)
+    }
+}
+
+/// Randomly shifts each played back event by a number of frames, configured as a resource.
+///
+/// This perturbs the timing of played back input, to test how robust systems are to irregular input.
+/// Each event is shifted by a number of frames drawn from `frames` using the [`PlaybackRng`],
+/// and is sent that many updates before or after the update it would otherwise be played back on.
+/// The [`TimestampedInputs`] themselves are never changed, so every strategy is affected, whenever the recording was loaded.
+///
+/// To shift events earlier, playback looks ahead by up to `-frames.start()` updates,
+/// predicting when each upcoming event will be read from how far its recorded frame (or time) is from the events being read now.
+/// Events are never sent before playback reaches the start of the recording (or of the current range or loop),
+/// so negative shifts are clamped for the events within reach of the start, and when playback begins, resumes or seeks.
+/// [`PlaybackStrategy::Step`] cannot be predicted, and so its events are only ever shifted later.
+/// Events due on the same update keep the order in which they were read.
+///
+/// Events are counted against the [`PlaybackMaxEventsPerUpdate`] as they are read on their own update;
+/// events that were shifted earlier have already been sent by then, and are not counted.
+/// Delayed events are still sent after the [`PlaybackStrategy`] finishes or is paused.
+///
+/// By default, the range is `0..=0`, and events are not shifted.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct PlaybackJitter {
+    /// The range of frames that each event may be shifted by, where negative values shift events earlier
+    pub frames: RangeInclusive<i32>,
+}
+
+impl Default for PlaybackJitter {
+    fn default() -> Self {
+        PlaybackJitter { frames: 0..=0 }
+    }
+}
+
+/// The single source of randomness for every randomized playback behavior, such as [`PlaybackJitter`], configured as a resource.
+///
+/// Randomized playback never draws from any other source, so inserting this with a fixed seed
+/// makes perturbed replays reproducible: the same seed and recording always produce the same playback.
+/// By default, the seed is 0.
+#[derive(Resource, Debug, Clone)]
+pub struct PlaybackRng(pub fastrand::Rng);
+
+impl PlaybackRng {
+    /// Creates a random number generator that always produces the same sequence for the same `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        PlaybackRng(fastrand::Rng::with_seed(seed))
+    }
+}
+
+impl Default for PlaybackRng {
+    fn default() -> Self {
+        PlaybackRng::from_seed(0)
+    }
+}
+
+/// Redirects played back gamepad events from the recorded gamepad to another gamepad, configured as a resource.
+///
+/// The ids of physical gamepads often differ between sessions,
+/// so use this to send recorded events to whichever gamepad is connected when playing back.
+/// Connection and disconnection events are remapped too, so the remapped gamepad is connected and disconnected as recorded.
+/// Gamepads without an entry are played back unchanged.
+///
+/// By default, no gamepads are remapped.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Default)]
+pub struct GamepadRemap(pub HashMap<Gamepad, Gamepad>);
+
+impl GamepadRemap {
+    /// Redirects the `event` to its remapped gamepad, if any.
+    pub fn remap(&self, mut event: GamepadEvent) -> GamepadEvent {
+        let gamepad = match &mut event {
+            GamepadEvent::Connection(event) => &mut event.gamepad,
+            GamepadEvent::Button(event) => &mut event.gamepad,
+            GamepadEvent::Axis(event) => &mut event.gamepad,
+        };
+        if let Some(remapped) = self.0.get(gamepad) {
+            *gamepad = *remapped;
+        }
+        event
+    }
+}
+
+/// Redirects played back window events from the recorded window entity to a live window entity, configured as a resource.
+///
+/// Window entities differ between runs, so use this to send recorded [`CursorMoved`], [`WindowResized`] and [`WindowFocused`] events
+/// to a window that exists now.
+/// Windows without an entry are played back unchanged;
+/// events that refer to a window that does not exist are dropped, with a warning.
+///
+/// By default, no windows are remapped.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlaybackWindowRemap(pub HashMap<Entity, Entity>);
+
+impl PlaybackWindowRemap {
+    /// The live window entity that events recorded for `window` should be sent to.
+    pub fn remap(&self, window: Entity) -> Entity {
+        self.0.get(&window).copied().unwrap_or(window)
+    }
+}
+
+/// The input mechanisms played back via the [`InputPlaybackPlugin`], configured as a resource.
+///
+/// This mirrors [`InputModesCaptured`](crate::input_capture::InputModesCaptured):
+/// recorded events of a disabled mode are skipped rather than sent, so live input of that kind can be mixed with the recording.
+/// `AppExit` events are always played back.
+///
+/// By default, all supported input modes will be played back.
+#[derive(Resource, Debug, PartialEq, Eq, Clone)]
+pub struct PlaybackModes {
+    /// Mouse button inputs
+    pub mouse_buttons: bool,
+    /// Mouse wheel inputs
+    pub mouse_wheel: bool,
+    /// Moving the mouse cursor, via [`CursorMoved`] events
+    pub mouse_motion: bool,
+    /// Relative [`MouseMotion`] deltas, as used for mouse look
+    pub mouse_deltas: bool,
+    /// Keyboard inputs
+    pub keyboard: bool,
+    /// Touchscreen inputs
+    pub touch: bool,
+    /// Gamepad inputs
+    ///
+    /// Plays back gamepad connections, button presses and axis values
+    pub gamepad: bool,
+    /// Window events
+    ///
+    /// Plays back [`WindowResized`] and [`WindowFocused`] events, sent to the window given by the [`PlaybackWindowRemap`].
+    /// Only the events are sent: the [`Window`] itself is not resized or focused.
+    pub window: bool,
+}
+
+impl PlaybackModes {
+    /// Disables all input playback
+    pub const DISABLE_ALL: PlaybackModes = PlaybackModes {
+        mouse_buttons: false,
+        mouse_wheel: false,
+        mouse_motion: false,
+        mouse_deltas: false,
+        keyboard: false,
+        touch: false,
+        gamepad: false,
+        window: false,
+    };
+
+    /// Plays back all supported input modes
+    pub const ENABLE_ALL: PlaybackModes = PlaybackModes {
+        mouse_buttons: true,
+        mouse_wheel: true,
+        mouse_motion: true,
+        mouse_deltas: true,
+        keyboard: true,
+        touch: true,
+        gamepad: true,
+        window: true,
+    };
+
+    /// Should the provided `input_event` be played back?
+    pub fn plays(&self, input_event: &InputEvent) -> bool {
+        match input_event {
+            InputEvent::Keyboard(_) => self.keyboard,
+            InputEvent::MouseButton(_) => self.mouse_buttons,
+            InputEvent::MouseWheel(_) => self.mouse_wheel,
+            InputEvent::CursorMoved(_) => self.mouse_motion,
+            InputEvent::MouseMotion(_) => self.mouse_deltas,
+            InputEvent::Touch(_) => self.touch,
+            InputEvent::Gamepad(_) => self.gamepad,
+            InputEvent::WindowResized(_) | InputEvent::WindowFocused(_) => self.window,
+            InputEvent::AppExit => true,
+        }
+    }
+}
+
+impl Default for PlaybackModes {
+    fn default() -> Self {
+        PlaybackModes::ENABLE_ALL
+    }
+}
+
 /// The [`EventWriter`] types that correspond to the input event types stored in [`InputEvent`](crate::timestamped_input::InputEvent)
 #[derive(SystemParam)]
 #[allow(missing_docs)]
@@ -99,15 +629,133 @@ pub struct InputWriters<'w, 's> {
     pub mouse_button_input: EventWriter<'w, MouseButtonInput>,
     pub mouse_wheel: EventWriter<'w, MouseWheel>,
     pub cursor_moved: EventWriter<'w, CursorMoved>,
+    pub mouse_motion: EventWriter<'w, MouseMotion>,
+    pub touch_input: EventWriter<'w, TouchInput>,
     pub windows: Query<'w, 's, &'static mut Window>,
+    pub primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
     pub gamepad: EventWriter<'w, GamepadEvent>,
+    pub window_resized: EventWriter<'w, WindowResized>,
+    pub window_focused: EventWriter<'w, WindowFocused>,
+    pub gamepad_remap: Res<'w, GamepadRemap>,
+    pub window_remap: Res<'w, PlaybackWindowRemap>,
+    pub playback_modes: Res<'w, PlaybackModes>,
+    pub max_events: Res<'w, PlaybackMaxEventsPerUpdate>,
+    pub scale_cursor: Res<'w, PlaybackScaleCursor>,
+    pub input_state: InputStateWriters<'w>,
+    pub jittered: JitteredEvents<'w, 's>,
     pub app_exit: EventWriter<'w, AppExit>,
 }
 
+/// The played back events that have been shifted by the [`PlaybackJitter`], waiting to be sent
+#[derive(SystemParam)]
+pub struct JitteredEvents<'w, 's> {
+    jitter: Res<'w, PlaybackJitter>,
+    rng: ResMut<'w, PlaybackRng>,
+    /// The number of updates on which events have been sent so far
+    updates: Local<'s, u64>,
+    /// Each shifted event, with the update it is due on, in the order they were queued
+    delayed: Local<'s, Vec<(u64, QueuedEvent)>>,
+    /// The index in the recording of the first event that has not yet been queued by looking ahead
+    queued_ahead_until: Local<'s, usize>,
+    /// The `cursor` of the recording once the last events were read, used to notice when playback moves back
+    last_cursor: Local<'s, usize>,
+}
+
+/// An event read from the recording, waiting to be sent
+struct QueuedEvent {
+    event: TimestampedInputEvent,
+    /// Where the cursor was when a mouse click was recorded, if it needs to be placed there first
+    cursor_moved: Option<CursorMoved>,
+}
+
+impl JitteredEvents<'_, '_> {
+    /// The number of updates that playback looks ahead by, so that events can be shifted earlier
+    fn lookahead(&self) -> u64 {
+        let frames = &self.jitter.frames;
+        if frames.is_empty() {
+            0
+        } else {
+            u64::from((-*frames.start()).max(0).unsigned_abs())
+        }
+    }
+
+    /// Queues the `event`, which would be played back in `ahead` updates, shifted by a number of updates drawn from the [`PlaybackJitter`].
+    ///
+    /// Events are never due before the current update.
+    fn shift(&mut self, ahead: u64, event: QueuedEvent) {
+        let frames = &self.jitter.frames;
+        let shift = if frames.is_empty() || *frames == (0..=0) {
+            0
+        } else {
+            self.rng.0.i32(frames.clone())
+        };
+        let due_on = (*self.updates + ahead).saturating_add_signed(i64::from(shift));
+        self.delayed.push((due_on.max(*self.updates), event));
+    }
+
+    /// Removes and returns every delayed event that is due on this update, and moves on to the next update.
+    fn take_due(&mut self) -> Vec<QueuedEvent> {
+        let updates = *self.updates;
+        *self.updates += 1;
+
+        let (due, delayed) = std::mem::take(&mut *self.delayed)
+            .into_iter()
+            .partition::<Vec<_>, _>(|&(due_on, _)| due_on <= updates);
+        *self.delayed = delayed;
+        due.into_iter().map(|(_, event)| event).collect()
+    }
+}
+
+/// The [`Input`] resources written to by playback when the [`PlaybackWriteInputState`] is enabled
+#[derive(SystemParam)]
+#[allow(missing_docs)]
+pub struct InputStateWriters<'w> {
+    pub write_input_state: Res<'w, PlaybackWriteInputState>,
+    pub keys: Option<ResMut<'w, Input<KeyCode>>>,
+    pub mouse_buttons: Option<ResMut<'w, Input<MouseButton>>>,
+    pub gamepad_buttons: Option<ResMut<'w, Input<GamepadButton>>>,
+}
+
+impl InputStateWriters<'_> {
+    /// Presses or releases the key or button affected by `input_event`, if the [`PlaybackWriteInputState`] is enabled.
+    pub fn apply(&mut self, input_event: &InputEvent) {
+        if !self.write_input_state.0 {
+            return;
+        }
+        let Some((input, pressed)) = ButtonlikeInput::from_event(input_event) else {
+            return;
+        };
+
+        fn set<T: Copy + Eq + std::hash::Hash + Send + Sync + 'static>(
+            state: Option<&mut ResMut<Input<T>>>,
+            input: T,
+            pressed: bool,
+        ) {
+            match state {
+                Some(state) if pressed => state.press(input),
+                Some(state) => state.release(input),
+                None => (),
+            }
+        }
+
+        match input {
+            ButtonlikeInput::Keyboard(key_code) => set(self.keys.as_mut(), key_code, pressed),
+            ButtonlikeInput::MouseButton(button) => {
+                set(self.mouse_buttons.as_mut(), button, pressed)
+            }
+            ButtonlikeInput::GamepadButton(button) => {
+                set(self.gamepad_buttons.as_mut(), button, pressed)
+            }
+        }
+    }
+}
+
 // `TimestampedInputs` is an iterator, so we need mutable access to be able to track which events we've seen
 /// A system that reads from the [`TimestampedInputs`] resources and plays back the contained events.
 ///
 /// The strategy used is based on [`PlaybackStrategy`].
+/// A [`PlaybackComplete`] event is sent each time a pass of playback finishes.
+#[allow(clippy::too_many_arguments)]
 pub fn playback_timestamped_input(
     mut timestamped_input: ResMut<TimestampedInputs>,
     mut playback_strategy: ResMut<PlaybackStrategy>,
@@ -115,112 +763,786 @@ pub fn playback_timestamped_input(
     frame_count: Res<FrameCount>,
     mut input_writers: InputWriters,
     mut playback_progress: ResMut<PlaybackProgress>,
+    playback_speed: Res<PlaybackSpeed>,
+    mut playback_complete: EventWriter<PlaybackComplete>,
+    mut loop_completed: EventWriter<LoopCompleted>,
+    mut loops_completed: Local<u32>,
+    mut playlist_position: Local<usize>,
+    mut segment_passes: Local<u32>,
+    mut step_events: EventReader<StepPlayback>,
+    pause_when_unfocused: Res<PlaybackPauseWhenUnfocused>,
 ) {
+    if playback_strategy.is_changed() {
+        *loops_completed = 0;
+        *playlist_position = 0;
+        *segment_passes = 0;
+    }
+
+    let unfocused = input_writers
+        .primary_window
+        .get_single()
+        .ok()
+        .and_then(|entity| input_writers.windows.get(entity).ok())
+        .map_or(false, |window| !window.focused);
+    if pause_when_unfocused.0 && unfocused {
+        step_events.clear();
+        return;
+    }
+
+    let scaled_delta = playback_speed.scale(time.delta());
+    let strategy = playback_strategy.clone();
+    let had_remaining_events = timestamped_input.remaining() > 0;
+    let mut complete = false;
+    let mut looped = false;
+
     // We cannot store the iterator, as different opaque return types are used
-    match *playback_strategy {
+    match &strategy {
         PlaybackStrategy::Time => {
             let input_events = timestamped_input.iter_until_time(time.elapsed());
-            send_playback_events(input_events, &mut input_writers);
+            let lookahead = Lookahead::times(time.elapsed(), time.delta());
+            queue_playback_events(
+                input_events,
+                &mut timestamped_input,
+                &mut input_writers,
+                lookahead,
+            );
+            complete = had_remaining_events && timestamped_input.remaining() == 0;
         }
         PlaybackStrategy::FrameCount => {
             let input_events = timestamped_input.iter_until_frame(*frame_count);
-            send_playback_events(input_events, &mut input_writers);
-        }
-        PlaybackStrategy::TimeRangeOnce(start, end) => {
-            let input_events = timestamped_input.iter_between_times(
-                playback_progress.current_time(start),
-                playback_progress.next_time(time.delta(), start),
+            let lookahead = Lookahead::frames(*frame_count + FrameCount(1));
+            queue_playback_events(
+                input_events,
+                &mut timestamped_input,
+                &mut input_writers,
+                lookahead,
             );
-            send_playback_events(input_events, &mut input_writers);
-
+            complete = had_remaining_events && timestamped_input.remaining() == 0;
+        }
+        &PlaybackStrategy::TimeRangeOnce(start, end) => {
             // If we've covered the entire range, reset our progress
-            if playback_progress.current_time(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+            if playback_progress.current_time(start) >= end
+                && !deferred_between_times(&timestamped_input, start, end)
+            {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
                 // We only want to play back once, so pause.
                 *playback_strategy = PlaybackStrategy::Paused;
+            } else {
+                // Events at the end of the range are excluded, even if this update overshoots it
+                let next = playback_progress.next_time(scaled_delta, start).min(end);
+                let input_events = timestamped_input.iter_between_times(start, next);
+                let lookahead = Lookahead::Times {
+                    next,
+                    per_update: scaled_delta,
+                    range: start..end,
+                };
+                queue_playback_events(
+                    input_events,
+                    &mut timestamped_input,
+                    &mut input_writers,
+                    lookahead,
+                );
             }
         }
-        PlaybackStrategy::FrameRangeOnce(start, end) => {
-            let input_events = timestamped_input.iter_between_frames(
-                playback_progress.current_frame(start),
-                playback_progress.next_frame(start),
+        &PlaybackStrategy::FrameRangeOnce(start, end) => {
+            // Events on the end frame are excluded, even on the final update of the range
+            let next = playback_progress.next_frame(start).min(end);
+            let input_events = timestamped_input.iter_between_frames(start, next);
+            let lookahead = Lookahead::Frames {
+                next,
+                range: start..end,
+            };
+            queue_playback_events(
+                input_events,
+                &mut timestamped_input,
+                &mut input_writers,
+                lookahead,
             );
-            send_playback_events(input_events, &mut input_writers);
 
             // If we've covered the entire range, reset our progress
-            if playback_progress.current_frame(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+            if playback_progress.current_frame(start) > end
+                && !deferred_between_frames(&timestamped_input, start, end)
+            {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
                 // We only want to play back once, so pause.
                 *playback_strategy = PlaybackStrategy::Paused;
             }
         }
-        PlaybackStrategy::TimeRangeLoop(start, end) => {
-            let input_events = timestamped_input.iter_between_times(
-                playback_progress.current_time(start),
-                playback_progress.next_time(time.delta(), start),
+        &PlaybackStrategy::TimeRangeLoop(start, end) => {
+            // If we've covered the entire range, reset our progress.
+            // Nothing is played on this update, so the end of one loop is never merged with the start of the next.
+            if playback_progress.current_time(start) >= end
+                && !deferred_between_times(&timestamped_input, start, end)
+            {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
+                looped = true;
+            } else {
+                // Events at the end of the range are excluded, even if this update overshoots it
+                let next = playback_progress.next_time(scaled_delta, start).min(end);
+                let input_events = timestamped_input.iter_between_times(start, next);
+                let lookahead = Lookahead::Times {
+                    next,
+                    per_update: scaled_delta,
+                    range: start..end,
+                };
+                queue_playback_events(
+                    input_events,
+                    &mut timestamped_input,
+                    &mut input_writers,
+                    lookahead,
+                );
+            }
+        }
+        &PlaybackStrategy::FrameRangeLoop(start, end) => {
+            // Events on the end frame are excluded, even on the final update of the range
+            let next = playback_progress.next_frame(start).min(end);
+            let input_events = timestamped_input.iter_between_frames(start, next);
+            let lookahead = Lookahead::Frames {
+                next,
+                range: start..end,
+            };
+            queue_playback_events(
+                input_events,
+                &mut timestamped_input,
+                &mut input_writers,
+                lookahead,
             );
-            send_playback_events(input_events, &mut input_writers);
 
             // If we've covered the entire range, reset our progress
-            if playback_progress.current_time(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+            if playback_progress.current_frame(start) > end
+                && !deferred_between_frames(&timestamped_input, start, end)
+            {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
+                looped = true;
+            }
+        }
+        &PlaybackStrategy::FrameRangeLoopN(start, end, remaining) => {
+            if remaining == 0 {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
+                *playback_strategy = PlaybackStrategy::Paused;
+            } else {
+                // Events on the end frame are excluded, even on the final update of the range
+                let next = playback_progress.next_frame(start).min(end);
+                let input_events = timestamped_input.iter_between_frames(start, next);
+                let lookahead = Lookahead::Frames {
+                    next,
+                    range: start..end,
+                };
+                queue_playback_events(
+                    input_events,
+                    &mut timestamped_input,
+                    &mut input_writers,
+                    lookahead,
+                );
+
+                // If we've covered the entire range, reset our progress and count down the loops
+                if playback_progress.current_frame(start) > end
+                    && !deferred_between_frames(&timestamped_input, start, end)
+                {
+                    playback_progress.reset(&mut timestamped_input);
+                    looped = true;
+                    if remaining == 1 {
+                        complete = true;
+                        *playback_strategy = PlaybackStrategy::Paused;
+                    } else {
+                        // This is the same playback continuing, so the loop count should not be reset
+                        *playback_strategy.bypass_change_detection() =
+                            PlaybackStrategy::FrameRangeLoopN(start, end, remaining - 1);
+                    }
+                }
             }
         }
-        PlaybackStrategy::FrameRangeLoop(start, end) => {
-            let input_events = timestamped_input.iter_between_frames(
-                playback_progress.current_frame(start),
-                playback_progress.next_frame(start),
+        PlaybackStrategy::Playlist(ranges) => {
+            let is_empty = |&(start, end): &(FrameCount, FrameCount)| start >= end;
+            // Skip over empty ranges, as they have nothing to play
+            while ranges.get(*playlist_position).map_or(false, is_empty) {
+                *playlist_position += 1;
+            }
+
+            if let Some(&(start, end)) = ranges.get(*playlist_position) {
+                // Events on the end frame are excluded, even on the final update of the range
+                let next = playback_progress.next_frame(start).min(end);
+                let input_events = timestamped_input.iter_between_frames(start, next);
+                let lookahead = Lookahead::Frames {
+                    next,
+                    range: start..end,
+                };
+                queue_playback_events(
+                    input_events,
+                    &mut timestamped_input,
+                    &mut input_writers,
+                    lookahead,
+                );
+
+                // Once this range has been covered, move on to the next non-empty one
+                if playback_progress.current_frame(start) > end
+                    && !deferred_between_frames(&timestamped_input, start, end)
+                {
+                    playback_progress.reset(&mut timestamped_input);
+                    *playlist_position += 1;
+                    while ranges.get(*playlist_position).map_or(false, is_empty) {
+                        *playlist_position += 1;
+                    }
+                }
+            }
+
+            if *playlist_position >= ranges.len() {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
+                *playback_strategy = PlaybackStrategy::Paused;
+            }
+        }
+        PlaybackStrategy::Composite(segments) => {
+            // Skip over empty segments, as they have nothing to play
+            while segments
+                .get(*playlist_position)
+                .map_or(false, PlaybackSegment::is_empty)
+            {
+                *playlist_position += 1;
+            }
+
+            if let Some(segment) = segments.get(*playlist_position) {
+                let (start, end) = (segment.start, segment.end);
+                // Events on the end frame are excluded, even on the final update of the range
+                let next = playback_progress.next_frame(start).min(end);
+                let input_events = timestamped_input.iter_between_frames(start, next);
+                let lookahead = Lookahead::Frames {
+                    next,
+                    range: start..end,
+                };
+                queue_playback_events(
+                    input_events,
+                    &mut timestamped_input,
+                    &mut input_writers,
+                    lookahead,
+                );
+
+                // Once this pass has been covered, repeat the segment or move on to the next non-empty one
+                if playback_progress.current_frame(start) > end
+                    && !deferred_between_frames(&timestamped_input, start, end)
+                {
+                    playback_progress.reset(&mut timestamped_input);
+                    *segment_passes += 1;
+                    if segment
+                        .loops
+                        .map_or(false, |loops| *segment_passes >= loops)
+                    {
+                        *segment_passes = 0;
+                        *playlist_position += 1;
+                        while segments
+                            .get(*playlist_position)
+                            .map_or(false, PlaybackSegment::is_empty)
+                        {
+                            *playlist_position += 1;
+                        }
+                    } else {
+                        looped = true;
+                    }
+                }
+            }
+
+            if *playlist_position >= segments.len() {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
+                *playback_strategy = PlaybackStrategy::Paused;
+            }
+        }
+        PlaybackStrategy::RealTime => {
+            let read_until = playback_progress.next_time(scaled_delta, Duration::ZERO);
+            let input_events = timestamped_input.iter_until_time(read_until);
+            let lookahead = Lookahead::times(read_until, scaled_delta);
+            queue_playback_events(
+                input_events,
+                &mut timestamped_input,
+                &mut input_writers,
+                lookahead,
             );
-            send_playback_events(input_events, &mut input_writers);
 
-            // If we've covered the entire range, reset our progress
-            if playback_progress.current_frame(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+            if timestamped_input.remaining() == 0 {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
+                // We only want to play back once, so pause.
+                *playback_strategy = PlaybackStrategy::Paused;
+            }
+        }
+        &PlaybackStrategy::FitDuration(target) => {
+            if let Some((start, end)) = timestamped_input.time_range() {
+                let span = end - start;
+                let (input_events, lookahead) = if target.is_zero() {
+                    // Nothing can be stretched to fit no time at all, so play everything immediately
+                    let input_events = timestamped_input.iter_rest().into_iter().collect();
+                    (input_events, Lookahead::None)
+                } else {
+                    // How much recorded time passes per unit of real time
+                    let speed = span.as_secs_f64() / target.as_secs_f64();
+                    let per_update = scaled_delta.mul_f64(speed);
+                    let read_until = playback_progress.next_time(per_update, start);
+                    let input_events = timestamped_input
+                        .iter_until_time(read_until)
+                        .into_iter()
+                        .collect::<Vec<_>>();
+                    (input_events, Lookahead::times(read_until, per_update))
+                };
+                queue_playback_events(
+                    input_events,
+                    &mut timestamped_input,
+                    &mut input_writers,
+                    lookahead,
+                );
+            }
+
+            if timestamped_input.cursor >= timestamped_input.len() {
+                playback_progress.reset(&mut timestamped_input);
+                complete = true;
+                // We only want to play back once, so pause.
+                *playback_strategy = PlaybackStrategy::Paused;
             }
         }
+        PlaybackStrategy::Step => {
+            let n_steps = step_events.iter().count();
+            let input_events: Vec<_> = timestamped_input.by_ref().take(n_steps).collect();
+            queue_playback_events(
+                input_events,
+                &mut timestamped_input,
+                &mut input_writers,
+                Lookahead::None,
+            );
+            complete = had_remaining_events && timestamped_input.remaining() == 0;
+        }
         PlaybackStrategy::Paused => {
             // Do nothing
         }
     };
+    // Steps only accumulate while stepping
+    step_events.clear();
+    send_playback_events(&timestamped_input, &mut input_writers);
+
+    if looped {
+        *loops_completed += 1;
+        loop_completed.send(LoopCompleted {
+            iteration: *loops_completed,
+        });
+    }
+
+    if complete {
+        playback_complete.send(PlaybackComplete { strategy });
+    }
+}
+
+/// Discards live input events while playback is active, if [`PlaybackMixMode::Override`] is set.
+///
+/// This runs immediately before [`playback_timestamped_input`], so only played back events remain in the event queues.
+/// Gamepad events sent after playback, such as those from `bevy_gilrs`, are not discarded.
+#[allow(clippy::too_many_arguments)]
+pub fn suppress_live_input(
+    mix_mode: Res<PlaybackMixMode>,
+    playback_strategy: Res<PlaybackStrategy>,
+    mut keyboard_input: ResMut<Events<KeyboardInput>>,
+    mut mouse_button_input: ResMut<Events<MouseButtonInput>>,
+    mut mouse_wheel: ResMut<Events<MouseWheel>>,
+    mut cursor_moved: ResMut<Events<CursorMoved>>,
+    mut mouse_motion: ResMut<Events<MouseMotion>>,
+    mut touch_input: ResMut<Events<TouchInput>>,
+    mut gamepad: ResMut<Events<GamepadEvent>>,
+) {
+    if *mix_mode != PlaybackMixMode::Override || *playback_strategy == PlaybackStrategy::Paused {
+        return;
+    }
+
+    keyboard_input.clear();
+    mouse_button_input.clear();
+    mouse_wheel.clear();
+    cursor_moved.clear();
+    mouse_motion.clear();
+    touch_input.clear();
+    gamepad.clear();
+}
+
+/// Sorts a batch of events into the order in which they are played back.
+///
+/// Runs of consecutive events recorded on the same frame are sent in [`InputKind`](crate::timestamped_input::InputKind) order
+/// (keyboard, then mouse, then touch, then gamepad),
+/// while events of the same kind keep their recorded order.
+/// Events from different frames are never reordered.
+///
+/// This makes the order in which events are sent independent of how they happened to be captured,
+/// so repeated replays of the same recording are identical.
+pub fn sort_for_playback(timestamped_input_events: &mut [TimestampedInputEvent]) {
+    sort_by_kind_within_frames(timestamped_input_events, |event| event);
 }
 
-fn send_playback_events(
+/// Sorts `items` as [`sort_for_playback`] would sort the event of each item.
+fn sort_by_kind_within_frames<T>(items: &mut [T], event: impl Fn(&T) -> &TimestampedInputEvent) {
+    let mut rest = items;
+    while let Some(first) = rest.first() {
+        let frame = event(first).frame;
+        let run_len = rest
+            .iter()
+            .position(|item| event(item).frame != frame)
+            .unwrap_or(rest.len());
+        let (frame_items, later) = rest.split_at_mut(run_len);
+        // This sort is stable, preserving the recorded order of events of the same kind
+        frame_items.sort_by_key(|item| event(item).input_event.kind());
+        rest = later;
+    }
+}
+
+/// How a [`PlaybackStrategy`] will carry on reading the recording on later updates
+///
+/// This predicts when upcoming events will be read, so that the [`PlaybackJitter`] can shift them earlier.
+#[derive(Debug, Clone)]
+enum Lookahead {
+    /// Events from the `next` frame onwards have not been read yet, and one more frame is read on each update.
+    /// Only events within the `range` are ever read.
+    Frames {
+        next: FrameCount,
+        range: std::ops::Range<FrameCount>,
+    },
+    /// Events from the `next` time onwards have not been read yet, and `per_update` more time is read on each update.
+    /// Only events within the `range` are ever read.
+    Times {
+        next: Duration,
+        per_update: Duration,
+        range: std::ops::Range<Duration>,
+    },
+    /// When later events will be read cannot be predicted
+    None,
+}
+
+impl Lookahead {
+    /// Events from the `next` frame onwards have not been read yet, and every frame will eventually be read
+    fn frames(next: FrameCount) -> Self {
+        Lookahead::Frames {
+            next,
+            range: FrameCount(0)..FrameCount(u64::MAX),
+        }
+    }
+
+    /// Events after the time `read_until` have not been read yet, and every time will eventually be read
+    fn times(read_until: Duration, per_update: Duration) -> Self {
+        Lookahead::Times {
+            next: read_until + Duration::from_nanos(1),
+            per_update,
+            range: Duration::ZERO..Duration::MAX,
+        }
+    }
+
+    /// Is the `event` within the part of the recording that will be read?
+    fn reads(&self, event: &TimestampedInputEvent) -> bool {
+        match self {
+            Lookahead::Frames { range, .. } => range.contains(&event.frame),
+            Lookahead::Times { range, .. } => range.contains(&event.time_since_startup),
+            Lookahead::None => false,
+        }
+    }
+
+    /// The number of updates until the unread `event` will be read, if it can be predicted
+    fn updates_until(&self, event: &TimestampedInputEvent) -> Option<u64> {
+        match self {
+            Lookahead::Frames { next, .. } => Some(event.frame.0.saturating_sub(next.0) + 1),
+            Lookahead::Times {
+                next, per_update, ..
+            } if !per_update.is_zero() => {
+                let ahead = event.time_since_startup.saturating_sub(*next);
+                Some((ahead.as_secs_f64() / per_update.as_secs_f64()) as u64 + 1)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Queues the events that were just read from the `recording` to be sent, up to the [`PlaybackMaxEventsPerUpdate`].
+///
+/// The events must be the last ones that the `cursor` of the `recording` moved past,
+/// so that any deferred events can be read again on the next update by moving the `cursor` back.
+/// Each event is shifted according to the [`PlaybackJitter`]:
+/// to shift events earlier, upcoming events are also queued ahead of time, predicting when they will be read with the `lookahead`.
+fn queue_playback_events(
     timestamped_input_events: impl IntoIterator<Item = TimestampedInputEvent>,
+    recording: &mut TimestampedInputs,
     input_writers: &mut InputWriters,
+    lookahead: Lookahead,
 ) {
-    for timestamped_input_event in timestamped_input_events {
+    let mut timestamped_input_events: Vec<_> = timestamped_input_events.into_iter().collect();
+    let jittered = &mut input_writers.jittered;
+
+    // When playback moves back, such as at the start of each loop, nothing after the cursor has been queued yet
+    let first_index = recording.cursor - timestamped_input_events.len();
+    if first_index < *jittered.last_cursor {
+        *jittered.queued_ahead_until = first_index;
+    }
+    // Events that were queued ahead of time have already been shifted, and are not counted again
+    let n_queued_ahead = jittered
+        .queued_ahead_until
+        .saturating_sub(first_index)
+        .min(timestamped_input_events.len());
+
+    let max_events = input_writers.max_events.0.max(1);
+    let mut n_played = 0;
+    let first_deferred = timestamped_input_events[n_queued_ahead..]
+        .iter()
+        .position(|event| {
+            let plays = input_writers.playback_modes.plays(&event.input_event);
+            n_played += usize::from(plays);
+            plays && n_played > max_events
+        });
+    if let Some(first_deferred) = first_deferred {
+        let first_deferred = n_queued_ahead + first_deferred;
+        recording.cursor -= timestamped_input_events.len() - first_deferred;
+        timestamped_input_events.truncate(first_deferred);
+    }
+    *jittered.last_cursor = recording.cursor;
+
+    // Clicks must land where they were recorded, even if the cursor movement before them is not played back,
+    // so the last recorded cursor movement is tracked as the events are read.
+    // It is only looked up in the recording for clicks before the first movement.
+    let mut last_cursor_moved = None;
+    for (offset, event) in timestamped_input_events
+        .into_iter()
+        .enumerate()
+        .skip(n_queued_ahead)
+    {
+        let index = first_index + offset;
+        let cursor_moved =
+            cursor_for_click(&mut last_cursor_moved, recording, index, input_writers);
+        if input_writers.playback_modes.plays(&event.input_event) {
+            input_writers.jittered.shift(
+                0,
+                QueuedEvent {
+                    event,
+                    cursor_moved,
+                },
+            );
+        }
+    }
+
+    // Deferred events are read again before anything after them, so nothing is queued ahead of them
+    let lookahead_updates = input_writers.jittered.lookahead();
+    if first_deferred.is_some() || lookahead_updates == 0 {
+        return;
+    }
+    let mut index = recording
+        .cursor
+        .max(*input_writers.jittered.queued_ahead_until);
+    let mut last_cursor_moved = None;
+    while let Some(event) = recording.events.get(index) {
+        let Some(ahead) = lookahead
+            .updates_until(event)
+            .filter(|&ahead| ahead <= lookahead_updates)
+        else {
+            break;
+        };
+
+        let cursor_moved =
+            cursor_for_click(&mut last_cursor_moved, recording, index, input_writers);
+        if lookahead.reads(event) && input_writers.playback_modes.plays(&event.input_event) {
+            input_writers.jittered.shift(
+                ahead,
+                QueuedEvent {
+                    event: event.clone(),
+                    cursor_moved,
+                },
+            );
+        }
+        index += 1;
+    }
+    *input_writers.jittered.queued_ahead_until = index;
+}
+
+/// Where the cursor should be placed before the event at `index` in the `recording` is played back, if it is a mouse click
+///
+/// The `last_cursor_moved` is the last cursor movement before `index`, once known,
+/// and must be passed in again for each consecutive event.
+fn cursor_for_click(
+    last_cursor_moved: &mut Option<Option<CursorMoved>>,
+    recording: &TimestampedInputs,
+    index: usize,
+    input_writers: &InputWriters,
+) -> Option<CursorMoved> {
+    match &recording.events[index].input_event {
+        InputEvent::CursorMoved(cursor_moved) => {
+            *last_cursor_moved = Some(Some(cursor_moved.clone()));
+            None
+        }
+        InputEvent::MouseButton(_) if input_writers.playback_modes.mouse_motion => {
+            last_cursor_moved
+                .get_or_insert_with(|| recording.cursor_moved_before(index))
+                .clone()
+        }
+        _ => None,
+    }
+}
+
+/// Sends every queued event that is due on this update.
+///
+/// This must be called exactly once per update, even if no new events were queued, so that delayed events are sent on time.
+fn send_playback_events(recording: &TimestampedInputs, input_writers: &mut InputWriters) {
+    let mut timestamped_input_events = input_writers.jittered.take_due();
+    sort_by_kind_within_frames(&mut timestamped_input_events, |queued| &queued.event);
+
+    let reference_resolution = recording
+        .metadata
+        .resolution
+        .filter(|resolution| input_writers.scale_cursor.0 && resolution.cmpgt(Vec2::ZERO).all());
+
+    // The recorded frame of the last cursor movement that was sent
+    let mut cursor_moved_on = None;
+    for queued in timestamped_input_events {
         use crate::timestamped_input::InputEvent::*;
+        let timestamped_input_event = queued.event;
+
+        // Movements recorded on the same frame are sent before clicks, and so have already placed the cursor
+        if let Some(cursor_moved) = queued.cursor_moved {
+            if cursor_moved_on != Some(timestamped_input_event.frame) {
+                send_cursor_moved(cursor_moved, reference_resolution, input_writers, true);
+            }
+        }
+
+        input_writers
+            .input_state
+            .apply(&timestamped_input_event.input_event);
+
         match timestamped_input_event.input_event {
             Keyboard(e) => input_writers.keyboard_input.send(e),
             MouseButton(e) => input_writers.mouse_button_input.send(e),
             MouseWheel(e) => input_writers.mouse_wheel.send(e),
-            // Window events MUST update the `Window` struct itself
-            // BLOCKED: https://github.com/bevyengine/bevy/issues/6163
             CursorMoved(e) => {
-                if let Ok(mut window) = input_writers.windows.get_mut(e.window) {
-                    window.set_cursor_position(Some(e.position));
-                } else {
-                    warn!("Window entity was not found when attempting to play back {e:?}")
+                cursor_moved_on = Some(timestamped_input_event.frame);
+                send_cursor_moved(e, reference_resolution, input_writers, false);
+            }
+            MouseMotion(e) => input_writers.mouse_motion.send(e),
+            Touch(mut e) => {
+                let primary_window = input_writers
+                    .primary_window
+                    .get_single()
+                    .ok()
+                    .and_then(|entity| input_writers.windows.get(entity).ok());
+                if let (Some(reference_resolution), Some(window)) =
+                    (reference_resolution, primary_window)
+                {
+                    e.position *= window_size(window) / reference_resolution;
                 }
-
-                input_writers.cursor_moved.send(e)
+                input_writers.touch_input.send(e);
             }
             Gamepad(e) => {
+                let e = input_writers.gamepad_remap.remap(e);
                 input_writers.gamepad.send(e);
             }
+            WindowResized(mut e) => {
+                e.window = input_writers.window_remap.remap(e.window);
+                if live_window_exists(e.window, input_writers, &e) {
+                    input_writers.window_resized.send(e);
+                }
+            }
+            WindowFocused(mut e) => {
+                e.window = input_writers.window_remap.remap(e.window);
+                if live_window_exists(e.window, input_writers, &e) {
+                    input_writers.window_focused.send(e);
+                }
+            }
             AppExit => input_writers.app_exit.send_default(),
         };
     }
 }
 
+/// Is the next event to be read from the `recording` between the `start` (inclusive) and `end` (exclusive) frames?
+///
+/// Once a range strategy has covered its whole range, this is only true when events were deferred by the [`PlaybackMaxEventsPerUpdate`],
+/// so the range must not finish until they have been played.
+fn deferred_between_frames(
+    recording: &TimestampedInputs,
+    start: FrameCount,
+    end: FrameCount,
+) -> bool {
+    recording
+        .current_framecount()
+        .map_or(false, |frame| start <= frame && frame < end)
+}
+
+/// Is the next event to be read from the `recording` between the `start` (inclusive) and `end` (exclusive) times?
+///
+/// The time-based equivalent of [`deferred_between_frames`].
+fn deferred_between_times(recording: &TimestampedInputs, start: Duration, end: Duration) -> bool {
+    recording
+        .current_time()
+        .map_or(false, |time| start <= time && time < end)
+}
+
+/// Moves the cursor of the window that `e` was recorded for, and sends `e`.
+///
+/// If a `reference_resolution` is provided, the position is scaled from it to the size of the window.
+/// If `only_if_moved` is set, nothing happens when the cursor is already at the recorded position.
+fn send_cursor_moved(
+    mut e: CursorMoved,
+    reference_resolution: Option<Vec2>,
+    input_writers: &mut InputWriters,
+    only_if_moved: bool,
+) {
+    e.window = input_writers.window_remap.remap(e.window);
+
+    // Window events MUST update the `Window` struct itself
+    // BLOCKED: https://github.com/bevyengine/bevy/issues/6163
+    if let Ok(mut window) = input_writers.windows.get_mut(e.window) {
+        let bounds = window_size(&window);
+        if let Some(reference_resolution) = reference_resolution {
+            e.position *= bounds / reference_resolution;
+        }
+        // The window may be smaller than when the recording was made
+        e.position = e.position.clamp(Vec2::ZERO, bounds);
+
+        let already_there = window
+            .cursor_position()
+            .map_or(false, |position| position.abs_diff_eq(e.position, 1e-3));
+        if only_if_moved && already_there {
+            return;
+        }
+
+        window.set_cursor_position(Some(e.position));
+        input_writers.cursor_moved.send(e)
+    } else {
+        // Events for windows that no longer exist cannot be handled, so are dropped
+        warn!("Window entity was not found when attempting to play back {e:?}")
+    }
+}
+
+/// The logical width and height of the `window`
+fn window_size(window: &Window) -> Vec2 {
+    Vec2::new(window.width(), window.height())
+}
+
+/// Does the `window` that the played back `event` is sent to exist?
+///
+/// Events for windows that no longer exist cannot be handled, so a warning is logged if not.
+fn live_window_exists(
+    window: Entity,
+    input_writers: &InputWriters,
+    event: &impl std::fmt::Debug,
+) -> bool {
+    let exists = input_writers.windows.contains(window);
+    if !exists {
+        warn!("Window entity was not found when attempting to play back {event:?}");
+    }
+    exists
+}
+
 /// Reads the stored file paths from the [`PlaybackFilePath`] location (if any)
+///
+/// # Panics
+///
+/// Panics if the file cannot be loaded.
+/// Use [`TimestampedInputs::read_ron`] directly to handle the [`LoadError`](crate::serde::LoadError) yourself.
 pub fn deserialize_timestamped_inputs(
     mut timestamped_inputs: ResMut<TimestampedInputs>,
     playback_path: Res<PlaybackFilePath>,
 ) {
     if let Some(file_path) = playback_path.path() {
-        let file = File::open(file_path).unwrap();
-        *timestamped_inputs = from_reader(file).unwrap();
+        *timestamped_inputs = TimestampedInputs::read_ron(file_path)
+            .unwrap_or_else(|err| panic!("Could not load {file_path:?}: {err}"));
     }
 }
 
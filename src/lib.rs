@@ -3,8 +3,17 @@
 #![warn(clippy::doc_markdown)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "leafwing-input-manager")]
+pub mod action_state;
+pub mod analysis;
+pub mod editing;
 pub mod frame_counting;
+pub mod hotkeys;
 pub mod input_capture;
 pub mod input_playback;
+pub mod library;
 pub mod serde;
+pub mod streaming;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod timestamped_input;
@@ -0,0 +1,120 @@
+//! Keeps several named recordings in memory, so that any of them can be played back.
+//!
+//! Only the live [`TimestampedInputs`] resource is captured into and played back;
+//! use [`RecordingLibrary::set_active`] to choose which stored recording it holds.
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::timestamped_input::TimestampedInputs;
+
+/// A collection of named [`TimestampedInputs`], stored as a resource.
+///
+/// This resource is added by the [`InputPlaybackPlugin`](crate::input_playback::InputPlaybackPlugin), and starts empty.
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub struct RecordingLibrary {
+    recordings: HashMap<String, TimestampedInputs>,
+    active: Option<String>,
+}
+
+impl RecordingLibrary {
+    /// Stores a recording under the provided `name`, returning the recording it replaced, if any.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        recording: TimestampedInputs,
+    ) -> Option<TimestampedInputs> {
+        self.recordings.insert(name.into(), recording)
+    }
+
+    /// Returns the recording stored under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&TimestampedInputs> {
+        self.recordings.get(name)
+    }
+
+    /// Removes and returns the recording stored under `name`, if any.
+    ///
+    /// The live [`TimestampedInputs`] are unaffected, even if this was the active recording.
+    pub fn remove(&mut self, name: &str) -> Option<TimestampedInputs> {
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        self.recordings.remove(name)
+    }
+
+    /// The names of every stored recording, in arbitrary order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.recordings.keys().map(String::as_str)
+    }
+
+    /// The name of the recording most recently passed to [`RecordingLibrary::set_active`], if it is still stored
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    /// Copies the recording stored under `name` into the `live` [`TimestampedInputs`], ready to be played back from the start.
+    ///
+    /// The stored copy is unchanged, so the same recording can be activated again later.
+    /// Returns `false`, leaving `live` unchanged, if there is no recording called `name`.
+    pub fn set_active(&mut self, name: &str, live: &mut TimestampedInputs) -> bool {
+        let Some(recording) = self.recordings.get(name) else {
+            return false;
+        };
+
+        live.clone_from(recording);
+        live.reset_cursor();
+        self.active = Some(name.to_string());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_counting::FrameCount;
+    use crate::timestamped_input::InputEvent;
+    use bevy::utils::Duration;
+
+    fn recording(frames: u64) -> TimestampedInputs {
+        let mut inputs = TimestampedInputs::default();
+        for frame in 0..frames {
+            inputs.send(FrameCount(frame), Duration::ZERO, InputEvent::AppExit);
+        }
+        inputs
+    }
+
+    #[test]
+    fn set_active_resets_cursor() {
+        let mut library = RecordingLibrary::default();
+        let mut stored = recording(3);
+        stored.seek_to_frame(FrameCount(2));
+        library.insert("intro", stored);
+        library.insert("boss", recording(5));
+
+        let mut live = TimestampedInputs::default();
+        assert!(library.set_active("intro", &mut live));
+        assert_eq!(library.active(), Some("intro"));
+        assert_eq!(live.len(), 3);
+        assert_eq!(live.current_framecount(), Some(FrameCount(0)));
+
+        assert!(!library.set_active("credits", &mut live));
+        assert_eq!(live.len(), 3);
+        assert_eq!(library.active(), Some("intro"));
+
+        assert!(library.set_active("boss", &mut live));
+        assert_eq!(live.len(), 5);
+    }
+
+    #[test]
+    fn remove_clears_active() {
+        let mut library = RecordingLibrary::default();
+        library.insert("intro", recording(1));
+
+        let mut live = TimestampedInputs::default();
+        library.set_active("intro", &mut live);
+        assert_eq!(library.remove("intro").map(|inputs| inputs.len()), Some(1));
+        assert_eq!(library.active(), None);
+        assert!(library.get("intro").is_none());
+        assert_eq!(live.len(), 1);
+    }
+}
@@ -1,6 +1,24 @@
-//! Serialization and deserialization of [`TimestampedInputs`](crate::timestamped_input::TimestampedInputs) data
-use bevy::ecs::prelude::Resource;
-use std::path::PathBuf;
+//! Serialization and deserialization of [`TimestampedInputs`] data
+use bevy::ecs::prelude::*;
+use bevy::log::error;
+use ron::ser::PrettyConfig;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::io;
+#[cfg(any(feature = "bincode", feature = "compression"))]
+use std::io::Read;
+#[cfg(any(feature = "bincode", feature = "compression", feature = "csv"))]
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use bevy::input::gamepad::{GamepadConnection, GamepadEvent};
+#[cfg(feature = "csv")]
+use bevy::input::{touch::TouchPhase, ButtonState};
+use std::fmt::Write as _;
+
+use crate::timestamped_input::{InputEvent, RecordingMetadata, TimestampedInputs};
+
 /// The file path where captured events will be saved to and read from.
 ///
 /// Currently, only `.ron` serialization / deserialization is supported.
@@ -46,3 +64,805 @@ impl PlaybackFilePath {
         self.path = path;
     }
 }
+
+impl TimestampedInputs {
+    /// Writes every recorded event to the `.ron` file at `path`, overwriting any existing file.
+    ///
+    /// The playback `cursor` is not saved: recordings always begin at their first event when loaded.
+    pub fn write_ron(&self, path: &Path) -> io::Result<()> {
+        let serialized = ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        std::fs::write(path, serialized)
+    }
+
+    /// Reads a recording from the `.ron` file at `path`, as written by [`TimestampedInputs::write_ron`].
+    ///
+    /// The returned recording's `cursor` is always 0, so playback begins from the first event.
+    pub fn read_ron(path: &Path) -> Result<Self, LoadError> {
+        Self::from_ron_bytes(&std::fs::read(path)?)
+    }
+}
+
+/// The bytes that begin every recording written by [`TimestampedInputs::write_bincode`]
+#[cfg(feature = "bincode")]
+pub const BINCODE_MAGIC: [u8; 4] = *b"LIPB";
+
+/// The version of the binary format written by [`TimestampedInputs::write_bincode`]
+///
+/// This is stored immediately after [`BINCODE_MAGIC`], and is bumped whenever the layout of recordings changes.
+/// Recordings written with any other version are rejected by [`TimestampedInputs::read_bincode`].
+#[cfg(feature = "bincode")]
+pub const BINCODE_FORMAT_VERSION: u8 = 3;
+
+#[cfg(feature = "bincode")]
+impl TimestampedInputs {
+    /// Writes every recorded event to the file at `path` in a compact binary format, overwriting any existing file.
+    ///
+    /// This is much smaller and faster to parse than [`TimestampedInputs::write_ron`], but is not human-readable.
+    /// The playback `cursor` is not saved.
+    pub fn write_bincode(&self, path: &Path) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(std::fs::File::create(path)?);
+        writer.write_all(&BINCODE_MAGIC)?;
+        writer.write_all(&[BINCODE_FORMAT_VERSION])?;
+        bincode::serialize_into(&mut writer, self)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        writer.flush()
+    }
+
+    /// Reads a recording from the file at `path`, as written by [`TimestampedInputs::write_bincode`].
+    ///
+    /// The returned recording's `cursor` is always 0, so playback begins from the first event.
+    pub fn read_bincode(path: &Path) -> Result<Self, LoadError> {
+        Self::from_bincode_bytes(&std::fs::read(path)?)
+    }
+
+    fn from_bincode_bytes(bytes: &[u8]) -> Result<Self, LoadError> {
+        let mut timestamped_inputs: TimestampedInputs =
+            bincode::deserialize(bincode_body(bytes)?).map_err(LoadError::DeserializeBincode)?;
+        timestamped_inputs.reset_cursor();
+
+        Ok(timestamped_inputs)
+    }
+}
+
+/// Checks the header of a binary recording, returning the serialized recording that follows it
+#[cfg(feature = "bincode")]
+fn bincode_body(bytes: &[u8]) -> Result<&[u8], LoadError> {
+    let body = bytes
+        .strip_prefix(&BINCODE_MAGIC)
+        .ok_or(LoadError::InvalidHeader)?;
+    let (&version, body) = body.split_first().ok_or(LoadError::InvalidHeader)?;
+    if version != BINCODE_FORMAT_VERSION {
+        return Err(LoadError::UnsupportedVersion(version));
+    }
+
+    Ok(body)
+}
+
+/// The bytes that begin every gzip stream, used to detect compressed recordings
+#[cfg(feature = "compression")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// How hard [`TimestampedInputs::write_compressed`] should work to shrink a recording
+#[cfg(feature = "compression")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Compress quickly, producing larger files
+    #[default]
+    Fast,
+    /// Compress as much as possible, at the cost of speed
+    Best,
+}
+
+#[cfg(feature = "compression")]
+impl From<Compression> for flate2::Compression {
+    fn from(compression: Compression) -> Self {
+        match compression {
+            Compression::Fast => flate2::Compression::fast(),
+            Compression::Best => flate2::Compression::best(),
+        }
+    }
+}
+
+#[cfg(feature = "compression")]
+impl TimestampedInputs {
+    /// Writes every recorded event to the file at `path` as gzip-compressed RON, overwriting any existing file.
+    ///
+    /// The playback `cursor` is not saved.
+    pub fn write_compressed(&self, path: &Path, compression: Compression) -> io::Result<()> {
+        let serialized = ron::ser::to_string(self)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let file = std::fs::File::create(path)?;
+        let mut encoder = flate2::write::GzEncoder::new(file, compression.into());
+        encoder.write_all(serialized.as_bytes())?;
+        encoder.finish()?;
+
+        Ok(())
+    }
+
+    /// Reads a recording from the gzip-compressed file at `path`, as written by [`TimestampedInputs::write_compressed`].
+    ///
+    /// The returned recording's `cursor` is always 0, so playback begins from the first event.
+    pub fn read_compressed(path: &Path) -> Result<Self, LoadError> {
+        let decompressed = decompress(&std::fs::read(path)?)?;
+        Self::from_ron_bytes(&decompressed)
+    }
+}
+
+#[cfg(feature = "compression")]
+fn decompress(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(bytes).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// The header row written by [`TimestampedInputs::write_csv`]
+#[cfg(feature = "csv")]
+pub const CSV_HEADER: &str = "frame,seconds,device,description,state,value";
+
+#[cfg(feature = "csv")]
+impl TimestampedInputs {
+    /// Writes one row per recorded event to the `.csv` file at `path`, overwriting any existing file.
+    ///
+    /// The columns are given by [`CSV_HEADER`]:
+    /// the frame, the time since startup in seconds, the device category,
+    /// a human-readable description of the input, the button state (or touch phase) and the axis or button value.
+    /// The `state` and `value` columns are left empty for events that have no such data.
+    ///
+    /// This is an export for analysis only: CSV files cannot be loaded as recordings.
+    pub fn write_csv(&self, path: &Path) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(writer, "{CSV_HEADER}")?;
+
+        for event in &self.events {
+            let row = CsvRow::from(&event.input_event);
+            writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                event.frame.0,
+                event.time_since_startup.as_secs_f64(),
+                row.device,
+                csv_field(&row.description),
+                row.state.unwrap_or_default(),
+                row.value.map(|value| value.to_string()).unwrap_or_default(),
+            )?;
+        }
+
+        writer.flush()
+    }
+}
+
+/// The columns of [`TimestampedInputs::write_csv`] that depend on the kind of event
+#[cfg(feature = "csv")]
+struct CsvRow {
+    device: &'static str,
+    description: String,
+    state: Option<&'static str>,
+    value: Option<f32>,
+}
+
+#[cfg(feature = "csv")]
+impl From<&InputEvent> for CsvRow {
+    fn from(input_event: &InputEvent) -> Self {
+        let button_state = |state: ButtonState| match state {
+            ButtonState::Pressed => "pressed",
+            ButtonState::Released => "released",
+        };
+        let row = |device, description: String| CsvRow {
+            device,
+            description,
+            state: None,
+            value: None,
+        };
+
+        match input_event {
+            InputEvent::Keyboard(event) => CsvRow {
+                state: Some(button_state(event.state)),
+                ..row(
+                    "keyboard",
+                    match event.key_code {
+                        Some(key_code) => format!("key {key_code:?}"),
+                        None => format!("scan code {}", event.scan_code),
+                    },
+                )
+            },
+            InputEvent::MouseButton(event) => CsvRow {
+                state: Some(button_state(event.state)),
+                ..row("mouse", format!("mouse button {:?}", event.button))
+            },
+            InputEvent::MouseWheel(event) => row(
+                "mouse",
+                format!("mouse wheel by ({}, {}) {:?}", event.x, event.y, event.unit),
+            ),
+            InputEvent::CursorMoved(event) => row(
+                "mouse",
+                format!(
+                    "cursor moved to ({}, {})",
+                    event.position.x, event.position.y
+                ),
+            ),
+            InputEvent::MouseMotion(event) => row(
+                "mouse",
+                format!("mouse moved by ({}, {})", event.delta.x, event.delta.y),
+            ),
+            InputEvent::Touch(event) => CsvRow {
+                state: Some(match event.phase {
+                    TouchPhase::Started => "started",
+                    TouchPhase::Moved => "moved",
+                    TouchPhase::Ended => "ended",
+                    TouchPhase::Cancelled => "cancelled",
+                }),
+                ..row(
+                    "touch",
+                    format!(
+                        "touch {} at ({}, {})",
+                        event.id, event.position.x, event.position.y
+                    ),
+                )
+            },
+            InputEvent::Gamepad(GamepadEvent::Connection(event)) => row(
+                "gamepad",
+                match &event.connection {
+                    GamepadConnection::Connected(info) => {
+                        format!("gamepad {} connected ({})", event.gamepad.id, info.name)
+                    }
+                    GamepadConnection::Disconnected => {
+                        format!("gamepad {} disconnected", event.gamepad.id)
+                    }
+                },
+            ),
+            InputEvent::Gamepad(GamepadEvent::Button(event)) => CsvRow {
+                value: Some(event.value),
+                ..row(
+                    "gamepad",
+                    format!(
+                        "gamepad {} button {:?}",
+                        event.gamepad.id, event.button_type
+                    ),
+                )
+            },
+            InputEvent::Gamepad(GamepadEvent::Axis(event)) => CsvRow {
+                value: Some(event.value),
+                ..row(
+                    "gamepad",
+                    format!("gamepad {} axis {:?}", event.gamepad.id, event.axis_type),
+                )
+            },
+            InputEvent::WindowResized(event) => row(
+                "window",
+                format!("window resized to {}x{}", event.width, event.height),
+            ),
+            InputEvent::WindowFocused(event) => row(
+                "window",
+                if event.focused {
+                    "window focused".to_string()
+                } else {
+                    "window unfocused".to_string()
+                },
+            ),
+            InputEvent::AppExit => row("app", "app exit".to_string()),
+        }
+    }
+}
+
+/// The one-line description of `input_event` used by [`TimestampedInputs::to_log_string`]
+fn log_description(input_event: &InputEvent) -> String {
+    match input_event {
+        InputEvent::Keyboard(event) => match event.key_code {
+            Some(key_code) => format!("KeyboardInput {{ {key_code:?} {:?} }}", event.state),
+            None => format!(
+                "KeyboardInput {{ scan code {} {:?} }}",
+                event.scan_code, event.state
+            ),
+        },
+        InputEvent::MouseButton(event) => {
+            format!(
+                "MouseButtonInput {{ {:?} {:?} }}",
+                event.button, event.state
+            )
+        }
+        InputEvent::MouseWheel(event) => format!(
+            "MouseWheel {{ ({}, {}) {:?} }}",
+            event.x, event.y, event.unit
+        ),
+        InputEvent::CursorMoved(event) => format!(
+            "CursorMoved {{ ({}, {}) in window {:?} }}",
+            event.position.x, event.position.y, event.window
+        ),
+        InputEvent::MouseMotion(event) => {
+            format!("MouseMotion {{ ({}, {}) }}", event.delta.x, event.delta.y)
+        }
+        InputEvent::Touch(event) => format!(
+            "TouchInput {{ {} {:?} at ({}, {}) }}",
+            event.id, event.phase, event.position.x, event.position.y
+        ),
+        InputEvent::Gamepad(GamepadEvent::Connection(event)) => match &event.connection {
+            GamepadConnection::Connected(info) => format!(
+                "GamepadConnection {{ {} Connected {:?} }}",
+                event.gamepad.id, info.name
+            ),
+            GamepadConnection::Disconnected => {
+                format!("GamepadConnection {{ {} Disconnected }}", event.gamepad.id)
+            }
+        },
+        InputEvent::Gamepad(GamepadEvent::Button(event)) => format!(
+            "GamepadButtonChanged {{ {} {:?} {} }}",
+            event.gamepad.id, event.button_type, event.value
+        ),
+        InputEvent::Gamepad(GamepadEvent::Axis(event)) => format!(
+            "GamepadAxisChanged {{ {} {:?} {} }}",
+            event.gamepad.id, event.axis_type, event.value
+        ),
+        InputEvent::WindowResized(event) => format!(
+            "WindowResized {{ {}x{} in window {:?} }}",
+            event.width, event.height, event.window
+        ),
+        InputEvent::WindowFocused(event) => format!(
+            "WindowFocused {{ {} in window {:?} }}",
+            if event.focused {
+                "focused"
+            } else {
+                "unfocused"
+            },
+            event.window
+        ),
+        InputEvent::AppExit => "AppExit".to_string(),
+    }
+}
+
+/// Quotes `field` if it contains characters that would otherwise break the CSV format
+#[cfg(feature = "csv")]
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl TimestampedInputs {
+    /// Describes each recorded event on its own line, for reading and diffing by hand.
+    ///
+    /// Lines look like `[frame 2 | 3.000s] KeyboardInput { F Pressed }`, giving the frame, the time since startup and the event.
+    /// This is an export for reading only: logs cannot be loaded as recordings.
+    pub fn to_log_string(&self) -> String {
+        let mut log = String::new();
+        for event in &self.events {
+            let _ = writeln!(
+                log,
+                "[frame {} | {:.3}s] {}",
+                event.frame.0,
+                event.time_since_startup.as_secs_f64(),
+                log_description(&event.input_event)
+            );
+        }
+        log
+    }
+
+    /// Reads a recording from the file at `path`, whatever format it was saved in.
+    ///
+    /// The format is detected from the contents of the file, rather than its extension:
+    /// gzip-compressed files (with the `compression` feature) and binary recordings (with the `bincode` feature)
+    /// are recognized by their leading bytes, and anything else is read as RON.
+    ///
+    /// The returned recording's `cursor` is always 0, so playback begins from the first event.
+    pub fn load(path: &Path) -> Result<Self, LoadError> {
+        Self::from_detected_bytes(&std::fs::read(path)?)
+    }
+
+    /// Reads only the [`RecordingMetadata`] of the recording in the file at `path`, whatever format it was saved in.
+    ///
+    /// Binary recordings store their metadata ahead of their events, so only the start of the file is read,
+    /// which is much cheaper than [`TimestampedInputs::load`] for listing the recordings on disk.
+    /// RON and compressed recordings are read and parsed in full, although their events are discarded rather than kept.
+    /// Recordings saved without metadata return the default metadata.
+    pub fn read_metadata(path: &Path) -> Result<RecordingMetadata, LoadError> {
+        #[cfg(feature = "bincode")]
+        {
+            let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+            let mut header = [0; BINCODE_MAGIC.len() + 1];
+            if reader.read_exact(&mut header).is_ok() && header.starts_with(&BINCODE_MAGIC) {
+                bincode_body(&header)?;
+                // The metadata is serialized first, so the events are never read
+                return bincode::deserialize_from(reader).map_err(LoadError::DeserializeBincode);
+            }
+        }
+
+        Self::metadata_from_detected_bytes(&std::fs::read(path)?)
+    }
+
+    fn metadata_from_detected_bytes(bytes: &[u8]) -> Result<RecordingMetadata, LoadError> {
+        #[cfg(feature = "compression")]
+        if bytes.starts_with(&GZIP_MAGIC) {
+            return Self::metadata_from_detected_bytes(&decompress(bytes)?);
+        }
+
+        /// Every other field of the recording is ignored
+        #[derive(Deserialize)]
+        struct MetadataOnly {
+            #[serde(default)]
+            metadata: RecordingMetadata,
+        }
+
+        let header: MetadataOnly = ron::de::from_bytes(bytes)?;
+        Ok(header.metadata)
+    }
+
+    fn from_detected_bytes(bytes: &[u8]) -> Result<Self, LoadError> {
+        #[cfg(feature = "compression")]
+        if bytes.starts_with(&GZIP_MAGIC) {
+            return Self::from_detected_bytes(&decompress(bytes)?);
+        }
+
+        #[cfg(feature = "bincode")]
+        if bytes.starts_with(&BINCODE_MAGIC) {
+            return Self::from_bincode_bytes(bytes);
+        }
+
+        Self::from_ron_bytes(bytes)
+    }
+
+    fn from_ron_bytes(bytes: &[u8]) -> Result<Self, LoadError> {
+        let mut timestamped_inputs: TimestampedInputs = ron::de::from_bytes(bytes)?;
+        timestamped_inputs.reset_cursor();
+
+        Ok(timestamped_inputs)
+    }
+}
+
+/// The reasons that loading a recording from disk with [`TimestampedInputs::read_ron`] (or any of the other loading methods) can fail
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file was read, but did not contain a valid recording
+    Deserialize(ron::error::SpannedError),
+    /// The file does not begin with [`BINCODE_MAGIC`], and so is not a binary recording
+    #[cfg(feature = "bincode")]
+    InvalidHeader,
+    /// The binary recording was written with a different [`BINCODE_FORMAT_VERSION`], which is stored here
+    #[cfg(feature = "bincode")]
+    UnsupportedVersion(u8),
+    /// The file has a valid header, but did not contain a valid binary recording
+    #[cfg(feature = "bincode")]
+    DeserializeBincode(bincode::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "could not read recording: {err}"),
+            LoadError::Deserialize(err) => write!(f, "could not deserialize recording: {err}"),
+            #[cfg(feature = "bincode")]
+            LoadError::InvalidHeader => write!(f, "file is not a binary recording"),
+            #[cfg(feature = "bincode")]
+            LoadError::UnsupportedVersion(version) => write!(
+                f,
+                "binary recording has format version {version}, but only version {BINCODE_FORMAT_VERSION} is supported"
+            ),
+            #[cfg(feature = "bincode")]
+            LoadError::DeserializeBincode(err) => {
+                write!(f, "could not deserialize binary recording: {err}")
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io(err) => Some(err),
+            LoadError::Deserialize(err) => Some(err),
+            #[cfg(feature = "bincode")]
+            LoadError::InvalidHeader | LoadError::UnsupportedVersion(_) => None,
+            #[cfg(feature = "bincode")]
+            LoadError::DeserializeBincode(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for LoadError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        LoadError::Deserialize(err)
+    }
+}
+
+/// Send this event to save the current [`TimestampedInputs`] to the provided `.ron` file on demand.
+///
+/// This is handled by the [`save_recording`] system, which is added by the [`InputCapturePlugin`](crate::input_capture::InputCapturePlugin).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveRecording(pub PathBuf);
+
+/// Writes the current [`TimestampedInputs`] to disk whenever a [`SaveRecording`] event is sent.
+///
+/// Failures are logged, rather than causing a panic.
+pub fn save_recording(
+    mut save_events: EventReader<SaveRecording>,
+    timestamped_inputs: Res<TimestampedInputs>,
+) {
+    for SaveRecording(path) in save_events.iter() {
+        if let Err(err) = timestamped_inputs.write_ron(path) {
+            error!("Could not save recording to {path:?}: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_counting::FrameCount;
+    use bevy::input::gamepad::{
+        Gamepad, GamepadAxisChangedEvent, GamepadAxisType, GamepadConnection,
+        GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    };
+    use bevy::input::keyboard::{KeyCode, KeyboardInput};
+    use bevy::input::mouse::{MouseButton, MouseButtonInput, MouseScrollUnit, MouseWheel};
+    use bevy::input::touch::{ForceTouch, TouchInput, TouchPhase};
+    use bevy::input::ButtonState;
+    use bevy::math::Vec2;
+    use bevy::utils::Duration;
+    use bevy::window::{CursorMoved, WindowFocused, WindowResized};
+
+    fn every_input_variant() -> TimestampedInputs {
+        let gamepad = Gamepad::new(1);
+        let mut inputs = TimestampedInputs::default();
+        let mut send = |event: crate::timestamped_input::InputEvent| {
+            let frame = FrameCount(inputs.len() as u64);
+            inputs.send(frame, Duration::from_nanos(frame.0 * 16_666_667), event);
+        };
+
+        send(
+            KeyboardInput {
+                scan_code: 17,
+                key_code: Some(KeyCode::W),
+                state: ButtonState::Pressed,
+            }
+            .into(),
+        );
+        send(
+            MouseButtonInput {
+                button: MouseButton::Other(7),
+                state: ButtonState::Released,
+            }
+            .into(),
+        );
+        send(
+            MouseWheel {
+                unit: MouseScrollUnit::Pixel,
+                x: 0.5,
+                y: -12.25,
+            }
+            .into(),
+        );
+        send(
+            CursorMoved {
+                window: Entity::from_raw(3),
+                position: Vec2::new(100.125, 0.1),
+            }
+            .into(),
+        );
+        send(
+            TouchInput {
+                phase: TouchPhase::Moved,
+                position: Vec2::new(1.0, 2.0),
+                force: Some(ForceTouch::Calibrated {
+                    force: 0.3,
+                    max_possible_force: 1.0,
+                    altitude_angle: Some(0.7),
+                }),
+                id: 4,
+            }
+            .into(),
+        );
+        send(
+            GamepadEvent::Connection(GamepadConnectionEvent::new(
+                gamepad,
+                GamepadConnection::Connected(GamepadInfo {
+                    name: "Test gamepad".to_string(),
+                }),
+            ))
+            .into(),
+        );
+        send(
+            GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                gamepad,
+                GamepadAxisType::LeftStickX,
+                -0.123_456_79,
+            ))
+            .into(),
+        );
+        send(
+            WindowResized {
+                window: Entity::from_raw(3),
+                width: 640.0,
+                height: 480.5,
+            }
+            .into(),
+        );
+        send(
+            WindowFocused {
+                window: Entity::from_raw(3),
+                focused: false,
+            }
+            .into(),
+        );
+        send(crate::timestamped_input::InputEvent::AppExit);
+        inputs.send_with_source(
+            FrameCount(100),
+            Duration::ZERO,
+            crate::timestamped_input::InputEvent::AppExit,
+            "test",
+        );
+
+        inputs
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        let path = std::env::temp_dir().join("leafwing_input_playback_bincode_round_trip.bin");
+        let mut inputs = every_input_variant();
+        inputs.cursor = 3;
+
+        inputs.write_bincode(&path).unwrap();
+        let loaded = TimestampedInputs::read_bincode(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        inputs.cursor = 0;
+        assert_eq!(loaded, inputs);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_metadata() {
+        let path = std::env::temp_dir().join("leafwing_input_playback_bincode_metadata.bin");
+        let mut inputs = every_input_variant();
+        inputs.metadata = RecordingMetadata::new("speedrun");
+        inputs
+            .metadata
+            .extra
+            .insert("version".to_string(), "1.2.3".to_string());
+
+        inputs.write_bincode(&path).unwrap();
+        let metadata = TimestampedInputs::read_metadata(&path).unwrap();
+
+        // The events are never read, so a file cut off after the metadata still works
+        let bytes = std::fs::read(&path).unwrap();
+        let header_len =
+            BINCODE_MAGIC.len() + 1 + bincode::serialized_size(&inputs.metadata).unwrap() as usize;
+        std::fs::write(&path, &bytes[..header_len]).unwrap();
+        let truncated_metadata = TimestampedInputs::read_metadata(&path).unwrap();
+        let truncated = TimestampedInputs::read_bincode(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(metadata, inputs.metadata);
+        assert_eq!(truncated_metadata, inputs.metadata);
+        assert!(matches!(truncated, Err(LoadError::DeserializeBincode(_))));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_rejects_other_versions() {
+        let path = std::env::temp_dir().join("leafwing_input_playback_bincode_versions.bin");
+        every_input_variant().write_bincode(&path).unwrap();
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[BINCODE_MAGIC.len()] = BINCODE_FORMAT_VERSION + 1;
+        std::fs::write(&path, &bytes).unwrap();
+        let newer = TimestampedInputs::read_bincode(&path);
+
+        std::fs::write(&path, "not a recording").unwrap();
+        let garbage = TimestampedInputs::read_bincode(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(
+            matches!(newer, Err(LoadError::UnsupportedVersion(v)) if v == BINCODE_FORMAT_VERSION + 1)
+        );
+        assert!(matches!(garbage, Err(LoadError::InvalidHeader)));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_round_trip() {
+        let dir = std::env::temp_dir();
+        let ron_path = dir.join("leafwing_input_playback_compressed.ron");
+        let gzip_path = dir.join("leafwing_input_playback_compressed.ron.gz");
+
+        // Repetitive recordings compress well
+        let mut inputs = TimestampedInputs::default();
+        for _ in 0..50 {
+            inputs.events.extend(every_input_variant().events);
+        }
+        inputs.write_ron(&ron_path).unwrap();
+
+        for compression in [Compression::Fast, Compression::Best] {
+            inputs.write_compressed(&gzip_path, compression).unwrap();
+            assert!(
+                std::fs::metadata(&gzip_path).unwrap().len()
+                    < std::fs::metadata(&ron_path).unwrap().len()
+            );
+            assert_eq!(
+                TimestampedInputs::read_compressed(&gzip_path).unwrap(),
+                inputs
+            );
+        }
+
+        // Both plain and compressed files can be loaded without knowing their format
+        assert_eq!(TimestampedInputs::load(&ron_path).unwrap(), inputs);
+        assert_eq!(TimestampedInputs::load(&gzip_path).unwrap(), inputs);
+
+        std::fs::remove_file(&ron_path).unwrap();
+        std::fs::remove_file(&gzip_path).unwrap();
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn load_detects_bincode() {
+        let path = std::env::temp_dir().join("leafwing_input_playback_load_bincode.bin");
+        let inputs = every_input_variant();
+
+        inputs.write_bincode(&path).unwrap();
+        let loaded = TimestampedInputs::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, inputs);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_export() {
+        let path = std::env::temp_dir().join("leafwing_input_playback_export.csv");
+        let inputs = every_input_variant();
+
+        inputs.write_csv(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let rows: Vec<_> = csv.lines().collect();
+        assert_eq!(rows.len(), inputs.len() + 1);
+        assert_eq!(rows[0], CSV_HEADER);
+        assert_eq!(rows[1], "0,0,keyboard,key W,pressed,");
+        assert_eq!(
+            rows[4],
+            "3,0.050000001,mouse,\"cursor moved to (100.125, 0.1)\",,"
+        );
+        assert_eq!(
+            rows[7],
+            "6,0.100000002,gamepad,gamepad 1 axis LeftStickX,,-0.12345679"
+        );
+    }
+
+    #[test]
+    fn log_string() {
+        let inputs = every_input_variant();
+        let log = inputs.to_log_string();
+
+        let lines: Vec<_> = log.lines().collect();
+        assert_eq!(lines.len(), inputs.len());
+        assert_eq!(lines[0], "[frame 0 | 0.000s] KeyboardInput { W Pressed }");
+        assert_eq!(
+            lines[3],
+            "[frame 3 | 0.050s] CursorMoved { (100.125, 0.1) in window 3v0 }"
+        );
+        assert_eq!(
+            lines[5],
+            r#"[frame 5 | 0.083s] GamepadConnection { 1 Connected "Test gamepad" }"#
+        );
+        assert_eq!(
+            lines[8],
+            "[frame 8 | 0.133s] WindowFocused { unfocused in window 3v0 }"
+        );
+        assert_eq!(lines[10], "[frame 100 | 0.000s] AppExit");
+
+        assert_eq!(TimestampedInputs::default().to_log_string(), "");
+    }
+}
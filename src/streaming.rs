@@ -0,0 +1,271 @@
+//! Streams recordings to and from disk one event at a time, so that memory use stays bounded during very long sessions.
+//!
+//! Streamed recordings are a sequence of records, each of which is a single [`TimestampedInputEvent`] serialized as RON,
+//! preceded by its length in bytes as a little-endian [`u32`].
+//! Use a [`RecordingStreamReader`] to read them back, either directly or via the [`StreamingPlaybackPlugin`].
+
+use bevy::app::{App, AppExit, CoreSet, Plugin};
+use bevy::ecs::prelude::*;
+use bevy::log::error;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::input_capture::capture_input;
+use crate::input_playback::playback_timestamped_input;
+use crate::serde::LoadError;
+use crate::timestamped_input::{TimestampedInputEvent, TimestampedInputs};
+
+/// Appends captured input to the file at `path` as it is recorded, rather than keeping it in memory
+///
+/// Each frame, the events captured into the [`TimestampedInputs`] resource are written out and then removed from it,
+/// so that resource only ever holds the events of the current frame.
+/// The file is flushed every `flush_interval` frames, and when an [`AppExit`] event is sent.
+///
+/// Requires the [`InputCapturePlugin`](crate::input_capture::InputCapturePlugin).
+///
+/// # Panics
+///
+/// Panics when the plugin is added if the file at `path` cannot be created.
+pub struct StreamingCapturePlugin {
+    /// The file that captured input is written to, which is overwritten if it already exists
+    pub path: PathBuf,
+    /// The number of frames between each flush of the file
+    pub flush_interval: u32,
+}
+
+impl StreamingCapturePlugin {
+    /// Streams captured input to the file at `path`, flushing once per second at 60 frames per second.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        StreamingCapturePlugin {
+            path: path.into(),
+            flush_interval: 60,
+        }
+    }
+}
+
+impl Plugin for StreamingCapturePlugin {
+    fn build(&self, app: &mut App) {
+        let file = File::create(&self.path).unwrap_or_else(|err| {
+            panic!(
+                "Could not create streaming capture file {:?}: {err}",
+                self.path
+            )
+        });
+
+        app.insert_resource(StreamingCapture {
+            writer: BufWriter::new(file),
+            flush_interval: self.flush_interval.max(1),
+            frames_since_flush: 0,
+        })
+        .add_system(
+            stream_captured_input
+                .in_base_set(CoreSet::Last)
+                .after(capture_input),
+        );
+    }
+}
+
+/// The open file that captured input is streamed to, added by the [`StreamingCapturePlugin`]
+#[derive(Resource, Debug)]
+pub struct StreamingCapture {
+    writer: BufWriter<File>,
+    flush_interval: u32,
+    frames_since_flush: u32,
+}
+
+impl StreamingCapture {
+    /// Writes any buffered events to disk immediately.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.frames_since_flush = 0;
+        self.writer.flush()
+    }
+}
+
+/// Moves newly captured events from the [`TimestampedInputs`] resource into the [`StreamingCapture`] file.
+///
+/// Failures are logged, rather than causing a panic.
+pub fn stream_captured_input(
+    mut streaming_capture: ResMut<StreamingCapture>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+    mut app_exit_events: EventReader<AppExit>,
+) {
+    let exiting = !app_exit_events.is_empty();
+    app_exit_events.clear();
+
+    let events = std::mem::take(&mut timestamped_input.events);
+    timestamped_input.reset_cursor();
+    timestamped_input.invalidate_index();
+
+    for event in &events {
+        if let Err(err) = write_record(&mut streaming_capture.writer, event) {
+            error!("Could not stream captured input: {err}");
+            return;
+        }
+    }
+
+    streaming_capture.frames_since_flush += 1;
+    if streaming_capture.frames_since_flush >= streaming_capture.flush_interval || exiting {
+        if let Err(err) = streaming_capture.flush() {
+            error!("Could not flush streamed input: {err}");
+        }
+    }
+}
+
+/// Writes a single `event` to `writer`, as a record of a streamed recording.
+pub fn write_record(writer: &mut impl Write, event: &TimestampedInputEvent) -> io::Result<()> {
+    let serialized = ron::ser::to_string(event)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let len = u32::try_from(serialized.len())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(serialized.as_bytes())
+}
+
+/// Reads the events of a streamed recording one at a time, as written by the [`StreamingCapturePlugin`]
+///
+/// This is an [`Iterator`] over each event in turn, which ends at the end of the stream.
+/// A record that is cut short (such as by a crash while it was being written) is reported as a [`LoadError::Io`].
+#[derive(Debug)]
+pub struct RecordingStreamReader<R> {
+    reader: R,
+}
+
+impl RecordingStreamReader<BufReader<File>> {
+    /// Opens the streamed recording at `path`.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Ok(RecordingStreamReader::new(BufReader::new(File::open(
+            path,
+        )?)))
+    }
+}
+
+impl<R: Read> RecordingStreamReader<R> {
+    /// Reads a streamed recording from `reader`.
+    pub fn new(reader: R) -> Self {
+        RecordingStreamReader { reader }
+    }
+}
+
+impl<R: Read> Iterator for RecordingStreamReader<R> {
+    type Item = Result<TimestampedInputEvent, LoadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut len = [0; 4];
+        // Only a stream that ends exactly between records has ended cleanly
+        match self.reader.read(&mut len[..1]) {
+            Ok(0) => return None,
+            Ok(_) => (),
+            Err(err) => return Some(Err(err.into())),
+        }
+
+        let record = (|| {
+            self.reader.read_exact(&mut len[1..])?;
+            let mut bytes = vec![0; u32::from_le_bytes(len) as usize];
+            self.reader.read_exact(&mut bytes)?;
+            Ok(ron::de::from_bytes(&bytes)?)
+        })();
+        Some(record)
+    }
+}
+
+/// Feeds a streamed recording from the file at `path` into the [`TimestampedInputs`] resource as it is played back
+///
+/// Rather than loading the whole recording at once, events are read ahead of playback `lookahead` at a time,
+/// and events that have been played back are discarded.
+/// As played back events are discarded, only strategies that play forwards through the recording once
+/// (such as [`PlaybackStrategy::FrameCount`](crate::input_playback::PlaybackStrategy::FrameCount)) are supported.
+///
+/// Requires the [`InputPlaybackPlugin`](crate::input_playback::InputPlaybackPlugin).
+///
+/// # Panics
+///
+/// Panics when the plugin is added if the file at `path` cannot be opened.
+pub struct StreamingPlaybackPlugin {
+    /// The streamed recording to play back
+    pub path: PathBuf,
+    /// The minimum number of unplayed events to keep loaded
+    ///
+    /// This must be larger than the number of events played back on any single frame.
+    pub lookahead: usize,
+}
+
+impl StreamingPlaybackPlugin {
+    /// Plays back the streamed recording at `path`, keeping 1024 events loaded ahead of playback.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        StreamingPlaybackPlugin {
+            path: path.into(),
+            lookahead: 1024,
+        }
+    }
+}
+
+impl Plugin for StreamingPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        let reader = RecordingStreamReader::open(&self.path).unwrap_or_else(|err| {
+            panic!("Could not open streamed recording {:?}: {err}", self.path)
+        });
+
+        app.insert_resource(StreamingPlayback {
+            reader: Some(reader),
+            lookahead: self.lookahead.max(1),
+        })
+        .add_system(
+            stream_playback_input
+                .in_base_set(CoreSet::First)
+                .before(playback_timestamped_input),
+        );
+    }
+}
+
+/// The streamed recording being played back, added by the [`StreamingPlaybackPlugin`]
+#[derive(Resource, Debug)]
+pub struct StreamingPlayback {
+    /// [`None`] once the end of the stream has been reached
+    reader: Option<RecordingStreamReader<BufReader<File>>>,
+    lookahead: usize,
+}
+
+impl StreamingPlayback {
+    /// Has every event in the stream been loaded?
+    pub fn is_finished(&self) -> bool {
+        self.reader.is_none()
+    }
+}
+
+/// Discards played back events from the [`TimestampedInputs`] resource, and tops it up from the [`StreamingPlayback`] file.
+///
+/// Failures are logged, and end the stream.
+pub fn stream_playback_input(
+    mut streaming_playback: ResMut<StreamingPlayback>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+) {
+    let lookahead = streaming_playback.lookahead;
+    if timestamped_input.remaining() >= lookahead {
+        return;
+    }
+    let Some(reader) = streaming_playback.reader.as_mut() else {
+        return;
+    };
+
+    let cursor = timestamped_input.cursor.min(timestamped_input.len());
+    timestamped_input.events.drain(..cursor);
+    timestamped_input.reset_cursor();
+    timestamped_input.invalidate_index();
+
+    while timestamped_input.len() < lookahead {
+        match reader.next() {
+            Some(Ok(event)) => timestamped_input.events.push(event),
+            Some(Err(err)) => {
+                error!("Could not read streamed recording: {err}");
+                streaming_playback.reader = None;
+                return;
+            }
+            None => {
+                streaming_playback.reader = None;
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,609 @@
+//! Utilities for testing and benchmarking apps that use input capture and playback.
+//!
+//! This module is only available when the `testing` feature is enabled.
+
+use bevy::app::{App, CoreSet, Plugin};
+use bevy::ecs::event::Event;
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{
+    Gamepad, GamepadAxis, GamepadAxisChangedEvent, GamepadAxisType, GamepadButtonChangedEvent,
+    GamepadButtonType, GamepadEvent,
+};
+use bevy::input::keyboard::{KeyCode, KeyboardInput};
+use bevy::input::mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::input::touch::{ForceTouch, TouchInput, TouchPhase};
+use bevy::input::{Axis, ButtonState, Input, InputPlugin};
+use bevy::math::Vec2;
+use bevy::time::{Time, TimeSystem, TimeUpdateStrategy};
+use bevy::utils::Duration;
+use bevy::window::{CursorMoved, WindowFocused, WindowPlugin, WindowResized};
+use bevy::MinimalPlugins;
+use std::fmt::Write;
+
+use crate::analysis::InputDiff;
+use crate::frame_counting::FrameCount;
+use crate::input_capture::{capture_input, InputCapturePlugin};
+use crate::input_playback::{InputPlaybackPlugin, PlaybackStrategy};
+use crate::timestamped_input::{InputEvent, InputKind, TimestampedInputs};
+
+/// The seed used by [`TimestampedInputs::synthetic`].
+///
+/// This is fixed so that synthetic recordings are identical from run to run.
+pub const SYNTHETIC_SEED: u64 = 0x1EAF_3140;
+
+/// The number of frames per second assumed when timestamping synthetic events
+const SYNTHETIC_FPS: u64 = 60;
+
+impl TimestampedInputs {
+    /// Generates a large, deterministic recording containing `num_events` events, drawn from the provided `kinds`.
+    ///
+    /// Events are spread over consecutive frames (starting at frame 1), with a few events per frame,
+    /// and timestamped as if the app were running at 60 frames per second.
+    /// The same arguments will always produce the same recording, so this is suitable for benchmarking.
+    ///
+    /// If `kinds` is empty, the returned recording will be empty.
+    pub fn synthetic(num_events: usize, kinds: &[InputKind]) -> TimestampedInputs {
+        let mut inputs = TimestampedInputs::default();
+        if kinds.is_empty() {
+            return inputs;
+        }
+
+        let mut rng = SplitMix64(SYNTHETIC_SEED);
+        let mut frame = FrameCount(1);
+        inputs.events.reserve(num_events);
+
+        for _ in 0..num_events {
+            // Advance roughly every other event, so that frames contain a realistic mix of events
+            if rng.coin() {
+                frame = frame + FrameCount(1 + rng.next() % 2);
+            }
+            let time_since_startup = Duration::from_nanos(frame.0 * 1_000_000_000 / SYNTHETIC_FPS);
+
+            let kind = kinds[rng.pick(kinds.len())];
+            inputs.send(frame, time_since_startup, synthetic_event(kind, &mut rng));
+        }
+
+        inputs
+    }
+}
+
+/// Sets up a headless app for deterministic, frame-based playback
+///
+/// This adds the [`MinimalPlugins`], [`WindowPlugin`], [`InputPlugin`] and [`InputPlaybackPlugin`],
+/// so it should be used in place of those plugins rather than alongside them.
+/// Playback starts with [`PlaybackStrategy::FrameCount`],
+/// and [`Time`] advances by exactly `frame_time` on each update rather than following the system clock.
+///
+/// Use [`update_and_collect`] to run the app and inspect the events that were played back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadlessPlaybackPlugin {
+    /// The amount of time that passes on each update
+    ///
+    /// Defaults to 1/60th of a second.
+    pub frame_time: Duration,
+}
+
+impl Default for HeadlessPlaybackPlugin {
+    fn default() -> Self {
+        HeadlessPlaybackPlugin {
+            frame_time: Duration::from_nanos(1_000_000_000 / SYNTHETIC_FPS),
+        }
+    }
+}
+
+impl Plugin for HeadlessPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(InputPlugin)
+            .add_plugin(InputPlaybackPlugin)
+            .insert_resource(PlaybackStrategy::FrameCount);
+
+        let frame_time = self.frame_time;
+        app.add_system(
+            (move |time: Res<Time>, mut update_strategy: ResMut<TimeUpdateStrategy>| {
+                let last_update = time.last_update().unwrap_or_else(|| time.startup());
+                *update_strategy = TimeUpdateStrategy::ManualInstant(last_update + frame_time);
+            })
+            .in_base_set(CoreSet::First)
+            .before(TimeSystem),
+        );
+    }
+}
+
+/// Updates the `app` `updates` times, returning every event of type `E` sent during those updates, in order.
+///
+/// Events sent before this is called are not included.
+pub fn update_and_collect<E: Event + Clone>(app: &mut App, updates: usize) -> Vec<E> {
+    let mut reader = app.world.resource::<Events<E>>().get_reader_current();
+    let mut collected = Vec::new();
+
+    // Events are only kept for two updates, so read them after every update
+    for _ in 0..updates {
+        app.update();
+        collected.extend(reader.iter(app.world.resource::<Events<E>>()).cloned());
+    }
+
+    collected
+}
+
+/// Panics unless `key` is currently pressed, according to the [`Input<KeyCode>`] resource of the `app`.
+#[track_caller]
+pub fn assert_key_pressed(app: &App, key: KeyCode) {
+    assert!(
+        app.world.resource::<Input<KeyCode>>().pressed(key),
+        "Expected {key:?} to be pressed, but it was released"
+    );
+}
+
+/// Panics unless `key` is currently released (not pressed), according to the [`Input<KeyCode>`] resource of the `app`.
+#[track_caller]
+pub fn assert_key_released(app: &App, key: KeyCode) {
+    assert!(
+        !app.world.resource::<Input<KeyCode>>().pressed(key),
+        "Expected {key:?} to be released, but it was pressed"
+    );
+}
+
+/// Panics unless `button` is currently pressed, according to the [`Input<MouseButton>`] resource of the `app`.
+#[track_caller]
+pub fn assert_mouse_button_pressed(app: &App, button: MouseButton) {
+    assert!(
+        app.world.resource::<Input<MouseButton>>().pressed(button),
+        "Expected {button:?} to be pressed, but it was released"
+    );
+}
+
+/// Panics unless `button` is currently released (not pressed), according to the [`Input<MouseButton>`] resource of the `app`.
+#[track_caller]
+pub fn assert_mouse_button_released(app: &App, button: MouseButton) {
+    assert!(
+        !app.world.resource::<Input<MouseButton>>().pressed(button),
+        "Expected {button:?} to be released, but it was pressed"
+    );
+}
+
+/// Panics unless the value of `axis` is within `1e-4` of `expected`, according to the [`Axis<GamepadAxis>`] resource of the `app`.
+///
+/// Axes that have never been set are treated as having a value of 0.
+#[track_caller]
+pub fn assert_gamepad_axis(app: &App, axis: GamepadAxis, expected: f32) {
+    let value = app
+        .world
+        .resource::<Axis<GamepadAxis>>()
+        .get(axis)
+        .unwrap_or_default();
+    assert!(
+        (value - expected).abs() <= 1e-4,
+        "Expected {:?} of {:?} to be {expected}, but it was {value}",
+        axis.axis_type,
+        axis.gamepad
+    );
+}
+
+/// Panics if `inputs` contains any device input recorded after `disabled_frame`, the frame that capture was disabled on.
+///
+/// Input recorded on `disabled_frame` itself is allowed. `AppExit` events are always captured, and so are ignored.
+/// On failure, the panic message lists every event that leaked past the end of capture.
+#[track_caller]
+pub fn assert_no_input_after(inputs: &TimestampedInputs, disabled_frame: FrameCount) {
+    let leaked: Vec<_> = inputs
+        .events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| {
+            event.frame > disabled_frame && !matches!(event.input_event, InputEvent::AppExit)
+        })
+        .collect();
+    if leaked.is_empty() {
+        return;
+    }
+
+    let mut message = format!(
+        "Input was captured after capture was disabled on frame {}:",
+        disabled_frame.0
+    );
+    for (index, event) in leaked {
+        let _ = write!(
+            message,
+            "\n  event {index} (frame {}): {:?}",
+            event.frame.0, event.input_event
+        );
+    }
+    panic!("{message}");
+}
+
+/// Plays back `inputs` in a headless app, captures the input that the app receives, and panics if it differs from `inputs`.
+///
+/// The app is built with the [`HeadlessPlaybackPlugin`] and [`InputCapturePlugin`],
+/// then passed to `configure` to add your own plugins, systems and resources.
+/// `inputs` are played back with [`PlaybackStrategy::FrameCount`], and the app is updated until every event has been played.
+///
+/// Only the recorded events and their order are compared, as the app runs on its own clock.
+/// On failure, the panic message lists every [`InputDiff`] along with the events involved.
+pub fn assert_replay_deterministic(configure: impl FnOnce(&mut App), inputs: &TimestampedInputs) {
+    let mut app = App::new();
+    app.add_plugin(HeadlessPlaybackPlugin::default())
+        .add_plugin(InputCapturePlugin)
+        .init_resource::<Recaptured>()
+        // Input is recaptured into a separate buffer, so the recording being played back is unchanged
+        .add_system(
+            swap_in_recaptured
+                .in_base_set(CoreSet::Last)
+                .before(capture_input),
+        )
+        .add_system(
+            swap_out_recaptured
+                .in_base_set(CoreSet::Last)
+                .after(capture_input),
+        );
+    configure(&mut app);
+
+    let mut playback = inputs.clone();
+    playback.reset_cursor();
+    let last_frame = playback.frame_range().map_or(0, |(_, end)| end.0);
+    app.insert_resource(playback)
+        .insert_resource(PlaybackStrategy::FrameCount);
+
+    // Frames are counted from 1, so allow an extra update to play events recorded on frame 0
+    for _ in 0..=last_frame + 1 {
+        if app.world.resource::<TimestampedInputs>().remaining() == 0 {
+            break;
+        }
+        app.update();
+    }
+
+    let recaptured = &app.world.resource::<Recaptured>().0;
+    let diffs = inputs.diff_within(recaptured, Duration::MAX);
+    if diffs.is_empty() {
+        return;
+    }
+
+    let mut message = format!(
+        "Replayed input differed from the recording in {} places:",
+        diffs.len()
+    );
+    for diff in diffs {
+        let _ = match diff {
+            InputDiff::Missing { index, frame, .. } => write!(
+                message,
+                "\n  missing event {index} (frame {}): {:?}",
+                frame.0, inputs.events[index].input_event
+            ),
+            InputDiff::Extra { index, frame, .. } => write!(
+                message,
+                "\n  extra event {index} (frame {}): {:?}",
+                frame.0, recaptured.events[index].input_event
+            ),
+            InputDiff::TimestampMismatch { .. } => Ok(()),
+        };
+    }
+    panic!("{message}");
+}
+
+/// The input captured by [`assert_replay_deterministic`]
+#[derive(Resource, Default)]
+struct Recaptured(TimestampedInputs);
+
+fn swap_in_recaptured(world: &mut World) {
+    swap_recaptured(world);
+}
+
+fn swap_out_recaptured(world: &mut World) {
+    swap_recaptured(world);
+}
+
+/// Exchanges the recording being played back with the recaptured input
+fn swap_recaptured(world: &mut World) {
+    world.resource_scope(|world, mut recaptured: Mut<Recaptured>| {
+        std::mem::swap(
+            &mut recaptured.0,
+            &mut *world.resource_mut::<TimestampedInputs>(),
+        );
+    });
+}
+
+fn synthetic_event(kind: InputKind, rng: &mut SplitMix64) -> InputEvent {
+    const KEYS: [KeyCode; 6] = [
+        KeyCode::W,
+        KeyCode::A,
+        KeyCode::S,
+        KeyCode::D,
+        KeyCode::Space,
+        KeyCode::LShift,
+    ];
+    const MOUSE_BUTTONS: [MouseButton; 3] =
+        [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+    const GAMEPAD_BUTTONS: [GamepadButtonType; 4] = [
+        GamepadButtonType::South,
+        GamepadButtonType::East,
+        GamepadButtonType::North,
+        GamepadButtonType::West,
+    ];
+    const GAMEPAD_AXES: [GamepadAxisType; 4] = [
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        GamepadAxisType::RightStickX,
+        GamepadAxisType::RightStickY,
+    ];
+
+    let state = if rng.coin() {
+        ButtonState::Pressed
+    } else {
+        ButtonState::Released
+    };
+
+    match kind {
+        InputKind::Keyboard => {
+            let index = rng.pick(KEYS.len());
+            InputEvent::Keyboard(KeyboardInput {
+                scan_code: index as u32,
+                key_code: Some(KEYS[index]),
+                state,
+            })
+        }
+        InputKind::MouseButton => InputEvent::MouseButton(MouseButtonInput {
+            button: MOUSE_BUTTONS[rng.pick(MOUSE_BUTTONS.len())],
+            state,
+        }),
+        InputKind::MouseWheel => InputEvent::MouseWheel(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: if state == ButtonState::Pressed {
+                1.0
+            } else {
+                -1.0
+            },
+        }),
+        InputKind::CursorMoved => InputEvent::CursorMoved(CursorMoved {
+            window: Entity::PLACEHOLDER,
+            position: Vec2::new(rng.unit() * 1280.0, rng.unit() * 720.0),
+        }),
+        InputKind::MouseMotion => InputEvent::MouseMotion(MouseMotion {
+            delta: Vec2::new(rng.unit() * 20.0 - 10.0, rng.unit() * 20.0 - 10.0),
+        }),
+        InputKind::Touch => {
+            const PHASES: [TouchPhase; 3] =
+                [TouchPhase::Started, TouchPhase::Moved, TouchPhase::Ended];
+            InputEvent::Touch(TouchInput {
+                phase: PHASES[rng.pick(PHASES.len())],
+                position: Vec2::new(rng.unit() * 1280.0, rng.unit() * 720.0),
+                force: Some(ForceTouch::Normalized(rng.unit() as f64)),
+                id: rng.pick(2) as u64,
+            })
+        }
+        InputKind::Gamepad => {
+            let gamepad = Gamepad::new(0);
+            if rng.coin() {
+                let button_type = GAMEPAD_BUTTONS[rng.pick(GAMEPAD_BUTTONS.len())];
+                let value = if state == ButtonState::Pressed {
+                    1.0
+                } else {
+                    0.0
+                };
+                InputEvent::Gamepad(GamepadEvent::Button(GamepadButtonChangedEvent::new(
+                    gamepad,
+                    button_type,
+                    value,
+                )))
+            } else {
+                let axis_type = GAMEPAD_AXES[rng.pick(GAMEPAD_AXES.len())];
+                InputEvent::Gamepad(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                    gamepad,
+                    axis_type,
+                    rng.unit() * 2.0 - 1.0,
+                )))
+            }
+        }
+        InputKind::WindowResized => InputEvent::WindowResized(WindowResized {
+            window: Entity::PLACEHOLDER,
+            width: 640.0 + rng.unit() * 640.0,
+            height: 360.0 + rng.unit() * 360.0,
+        }),
+        InputKind::WindowFocused => InputEvent::WindowFocused(WindowFocused {
+            window: Entity::PLACEHOLDER,
+            focused: state == ButtonState::Pressed,
+        }),
+        InputKind::AppExit => InputEvent::AppExit,
+    }
+}
+
+/// A tiny, dependency-free PRNG, used to generate synthetic recordings.
+///
+/// See <https://prng.di.unimi.it/splitmix64.c>.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A fair coin flip
+    fn coin(&mut self) -> bool {
+        self.next() & 1 == 0
+    }
+
+    /// A random index in `0..len`
+    fn pick(&mut self, len: usize) -> usize {
+        (self.next() % len as u64) as usize
+    }
+
+    /// A random value in `[0, 1)`
+    fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_capture::KeyFilter;
+    use crate::timestamped_input::SortingStrategy;
+
+    fn short_recording() -> TimestampedInputs {
+        let kinds = [
+            InputKind::Keyboard,
+            InputKind::MouseButton,
+            InputKind::Gamepad,
+        ];
+        TimestampedInputs::synthetic(20, &kinds)
+    }
+
+    #[test]
+    fn replay_is_deterministic() {
+        assert_replay_deterministic(|_| {}, &short_recording());
+    }
+
+    #[test]
+    #[should_panic(expected = "missing event")]
+    fn replay_not_deterministic() {
+        assert_replay_deterministic(
+            |app| {
+                app.insert_resource(KeyFilter::Allow(Default::default()));
+            },
+            &short_recording(),
+        );
+    }
+
+    #[test]
+    fn headless_playback() {
+        let mut app = App::new();
+        app.add_plugin(HeadlessPlaybackPlugin::default());
+
+        let mut inputs = TimestampedInputs::default();
+        for frame in 1..=3 {
+            inputs.send(
+                FrameCount(frame),
+                Duration::ZERO,
+                synthetic_event(InputKind::Keyboard, &mut SplitMix64(frame)),
+            );
+        }
+        app.insert_resource(inputs.clone());
+
+        let played: Vec<KeyboardInput> = update_and_collect(&mut app, 5);
+        let expected: Vec<KeyboardInput> = inputs
+            .events
+            .iter()
+            .filter_map(|event| event.input_event.as_keyboard().cloned())
+            .collect();
+        assert_eq!(played, expected);
+
+        // Time advances by exactly one frame per update
+        let elapsed = app.world.resource::<Time>().elapsed();
+        assert_eq!(elapsed, HeadlessPlaybackPlugin::default().frame_time * 5);
+    }
+
+    #[test]
+    fn input_assertions() {
+        let mut app = App::new();
+        app.add_plugin(HeadlessPlaybackPlugin::default());
+        let axis = GamepadAxis::new(Gamepad::new(0), GamepadAxisType::LeftStickX);
+
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(
+            FrameCount(1),
+            Duration::ZERO,
+            InputEvent::Keyboard(KeyboardInput {
+                scan_code: 33,
+                key_code: Some(KeyCode::F),
+                state: ButtonState::Pressed,
+            }),
+        );
+        inputs.send(
+            FrameCount(1),
+            Duration::ZERO,
+            InputEvent::MouseButton(MouseButtonInput {
+                button: MouseButton::Left,
+                state: ButtonState::Pressed,
+            }),
+        );
+        inputs.send(
+            FrameCount(1),
+            Duration::ZERO,
+            InputEvent::Gamepad(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                axis.gamepad,
+                axis.axis_type,
+                0.5,
+            ))),
+        );
+        app.insert_resource(inputs);
+
+        assert_key_released(&app, KeyCode::F);
+        app.update();
+        app.update();
+        assert_key_pressed(&app, KeyCode::F);
+        assert_key_released(&app, KeyCode::G);
+        assert_mouse_button_pressed(&app, MouseButton::Left);
+        assert_mouse_button_released(&app, MouseButton::Right);
+        assert_gamepad_axis(&app, axis, 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "event 3 (frame 4): MouseButton")]
+    fn assert_no_input_after_lists_leaked_events() {
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(2), Duration::ZERO, InputEvent::AppExit);
+        inputs.send(
+            FrameCount(2),
+            Duration::ZERO,
+            synthetic_event(InputKind::Keyboard, &mut SplitMix64(0)),
+        );
+        inputs.send(FrameCount(3), Duration::ZERO, InputEvent::AppExit);
+        assert_no_input_after(&inputs, FrameCount(3));
+
+        inputs.send(
+            FrameCount(4),
+            Duration::ZERO,
+            synthetic_event(InputKind::MouseButton, &mut SplitMix64(0)),
+        );
+        assert_no_input_after(&inputs, FrameCount(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected F to be pressed")]
+    fn assert_key_pressed_names_key() {
+        let mut app = App::new();
+        app.add_plugin(HeadlessPlaybackPlugin::default());
+        app.update();
+        assert_key_pressed(&app, KeyCode::F);
+    }
+
+    #[test]
+    fn synthetic_is_deterministic() {
+        let kinds = [InputKind::Keyboard, InputKind::Gamepad];
+        let first = TimestampedInputs::synthetic(1000, &kinds);
+        let second = TimestampedInputs::synthetic(1000, &kinds);
+
+        assert_eq!(first.len(), 1000);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn synthetic_respects_kinds() {
+        let kinds = [InputKind::MouseButton, InputKind::CursorMoved];
+        let inputs = TimestampedInputs::synthetic(500, &kinds);
+
+        assert!(inputs
+            .events
+            .iter()
+            .all(|event| kinds.contains(&event.input_event.kind())));
+        assert!(TimestampedInputs::is_sorted(
+            &inputs,
+            SortingStrategy::FrameCount
+        ));
+        assert!(TimestampedInputs::is_sorted(
+            &inputs,
+            SortingStrategy::TimeSinceStartup
+        ));
+    }
+
+    #[test]
+    fn synthetic_without_kinds_is_empty() {
+        let inputs = TimestampedInputs::synthetic(100, &[]);
+        assert!(inputs.is_empty());
+    }
+}
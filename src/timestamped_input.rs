@@ -4,12 +4,19 @@
 
 use bevy::app::AppExit;
 use bevy::ecs::prelude::*;
-use bevy::input::gamepad::GamepadEvent;
-use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::{MouseButtonInput, MouseWheel};
-use bevy::utils::Duration;
-use bevy::window::CursorMoved;
+use bevy::input::gamepad::{
+    GamepadAxis, GamepadAxisChangedEvent, GamepadAxisType, GamepadButtonChangedEvent, GamepadEvent,
+};
+use bevy::input::keyboard::{KeyCode, KeyboardInput};
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseWheel};
+use bevy::input::touch::TouchInput;
+use bevy::math::Vec2;
+use bevy::utils::{Duration, HashMap};
+use bevy::window::{CursorMoved, WindowFocused, WindowResized};
 use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use std::collections::BTreeMap;
+use std::time::SystemTime;
 
 use crate::frame_counting::FrameCount;
 
@@ -24,6 +31,93 @@ pub struct TimestampedInputEvent {
     pub time_since_startup: Duration,
     /// The [`InputEvent`] that was captured
     pub input_event: InputEvent,
+    /// An optional label describing where this event came from
+    ///
+    /// Events captured from the operating system by [`capture_input`](crate::input_capture::capture_input)
+    /// are labelled [`OS_SOURCE`]; other systems can tag the events they inject using [`TimestampedInputs::send_with_source`].
+    #[serde(default)]
+    pub source: Option<SmolStr>,
+}
+
+impl TimestampedInputEvent {
+    /// Creates a new [`TimestampedInputEvent`], without a [`source`](Self::source)
+    pub fn new(frame: FrameCount, time_since_startup: Duration, input_event: InputEvent) -> Self {
+        TimestampedInputEvent {
+            frame,
+            time_since_startup,
+            input_event,
+            source: None,
+        }
+    }
+}
+
+/// The [`TimestampedInputEvent::source`] of events that were captured from the operating system
+pub const OS_SOURCE: &str = "os";
+
+/// Information about the provenance of a recording, stored in [`TimestampedInputs::metadata`]
+///
+/// Other than the `resolution`, none of these fields are used by this crate, other than to save and load them with the recording.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordingMetadata {
+    /// A human-readable name for the recording
+    pub name: String,
+    /// When the recording was made, as an ISO 8601 timestamp
+    ///
+    /// [`RecordingMetadata::new`] sets this to the current time, in UTC.
+    pub recorded_at: String,
+    /// Arbitrary extra details, such as the game version or build that the recording was made with
+    pub extra: HashMap<String, String>,
+    /// The logical width and height of the primary window when capture began, if known
+    ///
+    /// This is recorded by [`record_capture_resolution`](crate::input_capture::record_capture_resolution),
+    /// and used to scale cursor and touch positions when the [`PlaybackScaleCursor`](crate::input_playback::PlaybackScaleCursor) is enabled.
+    #[serde(default)]
+    pub resolution: Option<Vec2>,
+}
+
+impl RecordingMetadata {
+    /// Creates metadata for a recording called `name`, made at the current time.
+    pub fn new(name: impl Into<String>) -> Self {
+        let since_epoch = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+
+        RecordingMetadata {
+            name: name.into(),
+            recorded_at: iso_8601_utc(since_epoch),
+            extra: HashMap::default(),
+            resolution: None,
+        }
+    }
+}
+
+/// Formats a time since the Unix epoch as an ISO 8601 timestamp, in UTC, to the nearest second
+fn iso_8601_utc(since_epoch: Duration) -> String {
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Converts a count of days into a civil date, following https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
 }
 
 /// A resource that stores the complete event-like list of [`TimestampedInputs`]
@@ -31,6 +125,11 @@ pub struct TimestampedInputEvent {
 /// Read and write to this struct when performing input capture and playback
 #[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TimestampedInputs {
+    /// Where this recording came from
+    ///
+    /// This is saved before the events, so it can be read on its own with [`TimestampedInputs::read_metadata`].
+    #[serde(default)]
+    pub metadata: RecordingMetadata,
     /// The underlying [`TimestampedInputEvent`] data
     ///
     /// New events are pushed to the back of the list.
@@ -44,10 +143,80 @@ pub struct TimestampedInputs {
     ///
     /// When iterating over this struct, iterate one item at a time, beginning at `cursor + 1`.
     /// When you are done iterating, update this cursor as the last read index.
+    ///
+    /// This is not serialized: deserialized recordings always start from the first event.
+    #[serde(skip)]
     pub cursor: usize,
+    /// A lazily-built lookup table used by [`TimestampedInputs::seek_to_frame`]
+    #[serde(skip)]
+    frame_index: FrameIndex,
+}
+
+/// Maps each recorded frame to the index of its first event, built by [`TimestampedInputs::build_index`]
+///
+/// This is a cache, and so never affects equality.
+#[derive(Debug, Clone, Default)]
+struct FrameIndex {
+    /// The number of events when the index was built, used to catch direct changes to `events`
+    len: usize,
+    first_events: Option<BTreeMap<FrameCount, usize>>,
+}
+
+impl FrameIndex {
+    fn invalidate(&mut self) {
+        self.first_events = None;
+    }
+}
+
+impl PartialEq for FrameIndex {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 impl TimestampedInputs {
+    /// Creates an empty recording with space for at least `capacity` events.
+    ///
+    /// Recording up to `capacity` events will not reallocate, which avoids hitches during long capture sessions.
+    pub fn with_capacity(capacity: usize) -> Self {
+        TimestampedInputs {
+            events: Vec::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a recording from events that only have a time since startup, such as logs exported by external tools.
+    ///
+    /// Each event is assigned to the frame that was being rendered at that time, when running at `hz` frames per second.
+    /// If `hz` is not finite and positive, every event is assigned to frame 0.
+    /// Events are stored in the order they are provided, and should be sorted by time.
+    pub fn from_durations(
+        events: impl IntoIterator<Item = (Duration, InputEvent)>,
+        hz: f32,
+    ) -> Self {
+        let hz = if hz.is_finite() && hz > 0.0 {
+            hz as f64
+        } else {
+            0.0
+        };
+        let mut inputs = TimestampedInputs::default();
+        for (time_since_startup, input_event) in events {
+            let frame = FrameCount((time_since_startup.as_secs_f64() * hz).floor() as u64);
+            inputs.send(frame, time_since_startup, input_event);
+        }
+        inputs
+    }
+
+    /// The time since startup and payload of every event, discarding their frames.
+    ///
+    /// This is the inverse of [`TimestampedInputs::from_durations`].
+    pub fn to_duration_only(&self) -> Vec<(Duration, InputEvent)> {
+        self.events
+            .iter()
+            .map(|event| (event.time_since_startup, event.input_event.clone()))
+            .collect()
+    }
+
     /// Records an `input_event`, making note of the frame and time that it was sent.
     pub fn send(
         &mut self,
@@ -55,10 +224,28 @@ impl TimestampedInputs {
         time_since_startup: Duration,
         input_event: InputEvent,
     ) {
-        self.events.push(TimestampedInputEvent {
+        self.frame_index.invalidate();
+        self.events.push(TimestampedInputEvent::new(
             frame,
             time_since_startup,
             input_event,
+        ));
+    }
+
+    /// Records an `input_event`, making note of the frame and time that it was sent, and labelling it with its `source`.
+    ///
+    /// Use this to distinguish input injected by your own systems from real input in a mixed recording.
+    pub fn send_with_source(
+        &mut self,
+        frame: FrameCount,
+        time_since_startup: Duration,
+        input_event: InputEvent,
+        source: impl Into<SmolStr>,
+    ) {
+        self.frame_index.invalidate();
+        self.events.push(TimestampedInputEvent {
+            source: Some(source.into()),
+            ..TimestampedInputEvent::new(frame, time_since_startup, input_event)
         });
     }
 
@@ -74,12 +261,224 @@ impl TimestampedInputs {
         }
     }
 
+    /// Records an `input_event` at the position in the list that keeps it sorted by [`FrameCount`], then by time since startup.
+    ///
+    /// Unlike [`TimestampedInputs::send`], this does not assume that the event happened after every existing event.
+    /// The event is placed after any existing events with the same timestamp.
+    /// If it is placed before the `cursor`, it is treated as already played back, and the cursor is moved to match.
+    /// The position is found with a binary search, and so the list must already be sorted.
+    pub fn insert(
+        &mut self,
+        input_event: impl Into<InputEvent>,
+        frame: FrameCount,
+        time_since_startup: Duration,
+    ) {
+        let index = self.events.partition_point(|event| {
+            (event.frame, event.time_since_startup) <= (frame, time_since_startup)
+        });
+        self.frame_index.invalidate();
+        self.events.insert(
+            index,
+            TimestampedInputEvent::new(frame, time_since_startup, input_event.into()),
+        );
+
+        if index < self.cursor {
+            self.cursor += 1;
+        }
+    }
+
+    /// Appends the events of `other` after the events in this recording.
+    ///
+    /// The events of `other` are shifted so that its first event lands on the frame after the last event in `self`,
+    /// whatever frame `other` began on. Their relative timings are preserved, so the combined recording stays sorted.
+    /// Times are shifted to match: the first event of `other` is placed one frame after the last event in `self`,
+    /// where a frame lasts as long as the average frame of `self` (or no time at all, if `self` only spans a single frame).
+    ///
+    /// If `self` is empty, the events of `other` are copied unchanged.
+    /// The `cursor` is not changed.
+    pub fn append(&mut self, other: &TimestampedInputs) {
+        self.frame_index.invalidate();
+        let (Some(first_event), Some(last_event)) = (self.events.first(), self.events.last())
+        else {
+            self.events = other.events.clone();
+            return;
+        };
+        let Some(other_first_event) = other.events.first() else {
+            return;
+        };
+
+        let frame_span = (last_event.frame - first_event.frame).0;
+        let frame_time = if frame_span == 0 {
+            Duration::ZERO
+        } else {
+            (last_event
+                .time_since_startup
+                .saturating_sub(first_event.time_since_startup))
+            .div_f64(frame_span as f64)
+        };
+        let first_frame = last_event.frame + FrameCount(1);
+        let first_time = last_event.time_since_startup + frame_time;
+
+        let other_start = (
+            other_first_event.frame,
+            other_first_event.time_since_startup,
+        );
+        self.events
+            .extend(other.events.iter().cloned().map(|mut event| {
+                event.frame = event.frame - other_start.0 + first_frame;
+                event.time_since_startup =
+                    event.time_since_startup.saturating_sub(other_start.1) + first_time;
+                event
+            }));
+    }
+
+    /// Shifts every event earlier, so that the first event is recorded at [`FrameCount(0)`](FrameCount) and [`Duration::ZERO`].
+    ///
+    /// The relative timings between events are preserved, and the `cursor` is not changed.
+    /// This does nothing if there are no events.
+    pub fn rebase(&mut self) {
+        let Some(first_event) = self.events.first() else {
+            return;
+        };
+
+        let frame_offset = first_event.frame;
+        let time_offset = first_event.time_since_startup;
+        for event in &mut self.events {
+            event.frame = event.frame - frame_offset;
+            event.time_since_startup = event.time_since_startup.saturating_sub(time_offset);
+        }
+        self.invalidate_index();
+    }
+
+    /// Reassigns the [`FrameCount`] of every event, converting a recording captured at `from_hz` frames per second to `to_hz`.
+    ///
+    /// Each event is moved to the frame that was being rendered at the target rate when it was recorded,
+    /// so when reducing the rate, events from several recorded frames collapse onto a single frame, in their recorded order.
+    /// The recorded time since startup of each event is unchanged, as is the `cursor`.
+    /// This does nothing unless both rates are finite and positive.
+    pub fn resample(&mut self, from_hz: f32, to_hz: f32) {
+        let is_valid = |hz: f32| hz.is_finite() && hz > 0.0;
+        if !is_valid(from_hz) || !is_valid(to_hz) {
+            return;
+        }
+
+        let ratio = to_hz as f64 / from_hz as f64;
+        for event in &mut self.events {
+            event.frame = FrameCount((event.frame.0 as f64 * ratio).floor() as u64);
+        }
+        self.invalidate_index();
+    }
+
+    /// Combines the events of `other` with the events in this recording, onto a single timeline.
+    ///
+    /// The combined events are sorted by [`FrameCount`], then by time since startup.
+    /// Events with identical timestamps keep their relative order, with the events of `self` first.
+    /// Each event of both recordings appears exactly once, and the `cursor` is reset to the start.
+    pub fn merge(&mut self, other: &TimestampedInputs) {
+        self.frame_index.invalidate();
+        self.events.extend(other.events.iter().cloned());
+        self.sort_stable();
+    }
+
+    /// Removes every event and resets the `cursor` to 0, ready for a fresh recording.
+    ///
+    /// Unlike replacing this with [`TimestampedInputs::default`], the allocated storage is kept for reuse.
+    /// The [`metadata`](TimestampedInputs::metadata) is reset.
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.metadata = RecordingMetadata::default();
+        self.reset_cursor();
+        self.invalidate_index();
+    }
+
+    /// Removes every event from the `cursor` onwards, keeping only the events that have already been played.
+    ///
+    /// Afterwards, the `cursor` is equal to the new [`len`](TimestampedInputs::len).
+    pub fn truncate_at_cursor(&mut self) {
+        self.events.truncate(self.cursor);
+        self.cursor = self.events.len();
+        self.invalidate_index();
+    }
+
     /// Resets the `cursor` to the beginning of the list, which tracks which events have been seen
     #[inline]
     pub fn reset_cursor(&mut self) {
         self.cursor = 0;
     }
 
+    /// Moves the `cursor` to the first event recorded on or after the provided `frame`, returning the new `cursor`.
+    ///
+    /// Frames before the start of the recording move the cursor to the start,
+    /// and frames after the end move it to the end.
+    /// No events are played back: the active [`PlaybackStrategy`](crate::input_playback::PlaybackStrategy)
+    /// will continue from the new position.
+    ///
+    /// The first seek builds a frame index (see [`TimestampedInputs::build_index`]), which makes repeated seeks cheap.
+    /// This should only be used on [`TimestampedInputs`] resources that are sorted by [`SortingStrategy::FrameCount`].
+    pub fn seek_to_frame(&mut self, frame: FrameCount) -> usize {
+        debug_assert!(TimestampedInputs::is_sorted(
+            self,
+            SortingStrategy::FrameCount
+        ));
+        self.build_index();
+        let first_events = self.frame_index.first_events.as_ref().unwrap();
+        self.cursor = first_events
+            .range(frame..)
+            .next()
+            .map_or(self.events.len(), |(_, &index)| index);
+        self.cursor
+    }
+
+    /// Builds the index used by [`TimestampedInputs::seek_to_frame`] ahead of time, if it is not already built.
+    ///
+    /// The index maps each recorded frame to its first event, and is otherwise built lazily by the first seek.
+    /// Call this before scrubbing through a long recording to avoid a hitch on the first seek.
+    ///
+    /// The index is discarded whenever events are added or reordered through the methods on [`TimestampedInputs`]
+    /// (including [`TimestampedInputs::edit`]) and rebuilt on the next seek.
+    /// If you modify `events` directly, call [`TimestampedInputs::invalidate_index`] afterwards.
+    pub fn build_index(&mut self) {
+        if self.frame_index.first_events.is_some() && self.frame_index.len == self.events.len() {
+            return;
+        }
+
+        let mut first_events = BTreeMap::new();
+        for (index, event) in self.events.iter().enumerate() {
+            first_events.entry(event.frame).or_insert(index);
+        }
+        self.frame_index = FrameIndex {
+            len: self.events.len(),
+            first_events: Some(first_events),
+        };
+    }
+
+    /// Discards the index built by [`TimestampedInputs::build_index`], so that it is rebuilt on the next seek.
+    ///
+    /// This is only needed after modifying `events` directly.
+    pub fn invalidate_index(&mut self) {
+        self.frame_index.invalidate();
+    }
+
+    /// Moves the `cursor` to the first event recorded at or after the provided `time` since startup, returning the new `cursor`.
+    ///
+    /// Times before the start of the recording move the cursor to the start,
+    /// and times after the end move it to the end.
+    ///
+    /// Seeking (in either direction) only repositions the cursor: no events are played back, even when seeking backwards.
+    /// The active [`PlaybackStrategy`](crate::input_playback::PlaybackStrategy) will emit events going forward from the new position.
+    ///
+    /// This is a binary search, and so should only be used on [`TimestampedInputs`] resources that are sorted by [`SortingStrategy::TimeSinceStartup`].
+    pub fn seek_to_duration(&mut self, time: Duration) -> usize {
+        debug_assert!(TimestampedInputs::is_sorted(
+            self,
+            SortingStrategy::TimeSinceStartup
+        ));
+        self.cursor = self
+            .events
+            .partition_point(|event| event.time_since_startup < time);
+        self.cursor
+    }
+
     /// Get the earliest and latest [`FrameCount`] of all events stored
     pub fn frame_range(&self) -> Option<(FrameCount, FrameCount)> {
         debug_assert!(self.is_sorted(SortingStrategy::FrameCount));
@@ -106,6 +505,26 @@ impl TimestampedInputs {
         }
     }
 
+    /// The time since startup of the last recorded event, or [`Duration::ZERO`] if there are no events
+    ///
+    /// As recordings begin at app startup, this is how long the recording takes to play back in full.
+    pub fn duration(&self) -> Duration {
+        self.events
+            .last()
+            .map(|event| event.time_since_startup)
+            .unwrap_or_default()
+    }
+
+    /// The number of frames between the first and last recorded events, or 0 if there are no events
+    ///
+    /// Unlike [`TimestampedInputs::frame_range`], this is a single span.
+    pub fn frame_len(&self) -> FrameCount {
+        match self.frame_range() {
+            Some((start, end)) => end - start,
+            None => FrameCount(0),
+        }
+    }
+
     /// Gets the total length of the event stream
     pub fn len(&self) -> usize {
         self.events.len()
@@ -116,6 +535,48 @@ impl TimestampedInputs {
         self.events.is_empty()
     }
 
+    /// How far through the recording playback has gotten, from 0.0 (no events played) to 1.0 (every event played).
+    ///
+    /// Empty recordings always return 0.0.
+    pub fn progress(&self) -> f32 {
+        if self.events.is_empty() {
+            return 0.0;
+        }
+
+        (self.cursor as f32 / self.events.len() as f32).clamp(0.0, 1.0)
+    }
+
+    /// The number of events that have not yet been played back.
+    pub fn remaining(&self) -> usize {
+        self.events.len().saturating_sub(self.cursor)
+    }
+
+    /// Returns an iterator over the events recorded on frames from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// This borrows the recording, and so never moves the `cursor`.
+    /// The range is found with a binary search, and so should only be used on [`TimestampedInputs`] resources
+    /// that are sorted by [`SortingStrategy::FrameCount`].
+    pub fn events_in_range(
+        &self,
+        start: FrameCount,
+        end: FrameCount,
+    ) -> impl Iterator<Item = &TimestampedInputEvent> {
+        debug_assert!(TimestampedInputs::is_sorted(
+            self,
+            SortingStrategy::FrameCount
+        ));
+        let start_index = self.events.partition_point(|event| event.frame < start);
+        let end_index = self.events.partition_point(|event| event.frame < end);
+        self.events[start_index..end_index.max(start_index)].iter()
+    }
+
+    /// Gets the event stored at `index`, or [`None`] if `index` is out of bounds.
+    ///
+    /// This does not move the `cursor`.
+    pub fn get(&self, index: usize) -> Option<&TimestampedInputEvent> {
+        self.events.get(index)
+    }
+
     /// Returns an iterator over all recorded events, beginning at the start of `events`.
     #[must_use]
     pub fn iter_all(&mut self) -> impl IntoIterator<Item = TimestampedInputEvent> {
@@ -144,14 +605,9 @@ impl TimestampedInputs {
             self,
             SortingStrategy::TimeSinceStartup
         ));
-        let mut result = Vec::with_capacity(self.events.len() - self.cursor);
-        while self.cursor < self.events.len()
-            && self.events[self.cursor].time_since_startup <= time_since_startup
-        {
-            result.push(self.events[self.cursor].clone());
-            self.cursor += 1;
-        }
-        result
+        let range =
+            self.advance_cursor_while(|event| event.time_since_startup <= time_since_startup);
+        self.events[range].to_vec()
     }
 
     /// Returns an iterator over all recorded events up to and including the provided `time_since_startup`, beginning at the current `cursor`
@@ -166,12 +622,8 @@ impl TimestampedInputs {
             self,
             SortingStrategy::FrameCount
         ));
-        let mut result = Vec::with_capacity(self.events.len() - self.cursor);
-        while self.cursor < self.events.len() && self.events[self.cursor].frame <= frame {
-            result.push(self.events[self.cursor].clone());
-            self.cursor += 1;
-        }
-        result
+        let range = self.advance_cursor_while(|event| event.frame <= frame);
+        self.events[range].to_vec()
     }
 
     /// Returns an iterator over recorded events starting from (inclusive) the start time,
@@ -188,17 +640,13 @@ impl TimestampedInputs {
             self,
             SortingStrategy::TimeSinceStartup
         ));
-        let mut result = Vec::with_capacity(self.events.len() - self.cursor);
-        while self.cursor < self.events.len() {
-            let cursor_time = self.events[self.cursor].time_since_startup;
-            if cursor_time >= start_time && cursor_time < end_time {
-                result.push(self.events[self.cursor].clone());
-            } else if cursor_time >= end_time {
-                break;
-            }
-            self.cursor += 1;
-        }
-        result
+        let range = self.advance_cursor_while(|event| event.time_since_startup < end_time);
+        // Events before the start of the range are skipped over, rather than played
+        self.events[range]
+            .iter()
+            .filter(|event| event.time_since_startup >= start_time)
+            .cloned()
+            .collect::<Vec<_>>()
     }
 
     /// Returns an iterator over recorded events starting from (inclusive) the start frame,
@@ -215,18 +663,119 @@ impl TimestampedInputs {
             self,
             SortingStrategy::FrameCount
         ));
-        let mut result = Vec::with_capacity(self.events.len());
-        while self.cursor < self.events.len() {
-            let cursor_frame = self.events[self.cursor].frame;
-
-            if cursor_frame >= start_frame && cursor_frame < end_frame {
-                result.push(self.events[self.cursor].clone());
-            } else if cursor_frame >= end_frame {
-                break;
+        let range = self.advance_cursor_while(|event| event.frame < end_frame);
+        // Events before the start of the range are skipped over, rather than played
+        self.events[range]
+            .iter()
+            .filter(|event| event.frame >= start_frame)
+            .cloned()
+            .collect::<Vec<_>>()
+    }
+
+    /// Moves the `cursor` past every following event for which `pred` is true, returning the range of events passed over.
+    ///
+    /// `pred` must be true for some prefix of the remaining events and false for the rest,
+    /// so that the new cursor can be found with a binary search.
+    fn advance_cursor_while(
+        &mut self,
+        pred: impl FnMut(&TimestampedInputEvent) -> bool,
+    ) -> std::ops::Range<usize> {
+        let start = self.cursor.min(self.events.len());
+        self.cursor = start + self.events[start..].partition_point(pred);
+        start..self.cursor
+    }
+
+    /// Rewrites the [`KeyCode`] of every recorded keyboard event according to the provided `key_codes` map.
+    ///
+    /// This is useful to play back a recording made with a different keyboard layout.
+    /// If `scan_codes` is provided, scan codes are rewritten according to that map as well;
+    /// otherwise they are left untouched.
+    /// Keys and scan codes without an entry in their map are never changed.
+    pub fn remap_keys(
+        &mut self,
+        key_codes: &HashMap<KeyCode, KeyCode>,
+        scan_codes: Option<&HashMap<u32, u32>>,
+    ) {
+        for event in self.events.iter_mut() {
+            if let InputEvent::Keyboard(keyboard_input) = &mut event.input_event {
+                if let Some(remapped) = keyboard_input
+                    .key_code
+                    .and_then(|key_code| key_codes.get(&key_code))
+                {
+                    keyboard_input.key_code = Some(*remapped);
+                }
+
+                if let Some(remapped) =
+                    scan_codes.and_then(|scan_codes| scan_codes.get(&keyboard_input.scan_code))
+                {
+                    keyboard_input.scan_code = *remapped;
+                }
+            }
+        }
+    }
+
+    /// Removes gamepad axis events whose value is within `epsilon` of the previous recorded value for the same axis.
+    ///
+    /// Each run of near-identical values for an axis collapses to its first event,
+    /// and values are always compared against the last event that was kept, so slow drifts are never lost entirely.
+    /// Events for other axes and inputs are never removed, and do not interrupt a run.
+    /// The `cursor` is reset to the start.
+    pub fn dedup_axes(&mut self, epsilon: f32) {
+        let mut last_values: HashMap<GamepadAxis, f32> = HashMap::default();
+        self.events.retain(|event| {
+            let InputEvent::Gamepad(GamepadEvent::Axis(axis_event)) = &event.input_event else {
+                return true;
+            };
+
+            let axis = GamepadAxis::new(axis_event.gamepad, axis_event.axis_type);
+            match last_values.get(&axis) {
+                Some(last_value) if (axis_event.value - last_value).abs() < epsilon => false,
+                _ => {
+                    last_values.insert(axis, axis_event.value);
+                    true
+                }
+            }
+        });
+        self.reset_cursor();
+        self.invalidate_index();
+    }
+
+    /// Subtracts the offset for each [`GamepadAxisType`] from every recorded value of that axis, clamping the result to `[-1.0, 1.0]`.
+    ///
+    /// Use this to correct a recording made on a controller whose sticks rest away from zero.
+    /// Axes without an offset, and all other events, are unchanged, as is the `cursor`.
+    pub fn recalibrate_axes(&mut self, offsets: HashMap<GamepadAxisType, f32>) {
+        for event in self.events.iter_mut() {
+            if let InputEvent::Gamepad(GamepadEvent::Axis(axis_event)) = &mut event.input_event {
+                if let Some(offset) = offsets.get(&axis_event.axis_type) {
+                    axis_event.value = (axis_event.value - offset).clamp(-1.0, 1.0);
+                }
             }
-            self.cursor += 1;
         }
-        result
+    }
+
+    /// Calls `f` on the frame, time since startup and event of every recorded input, allowing each to be edited in place.
+    ///
+    /// If any frame or time was changed, the input stream is then re-sorted with [`TimestampedInputs::sort_stable`],
+    /// which resets the `cursor` to 0. Otherwise, the `cursor` is unchanged.
+    pub fn for_each_mut<F: FnMut(&mut FrameCount, &mut Duration, &mut InputEvent)>(
+        &mut self,
+        mut f: F,
+    ) {
+        let mut retimed = false;
+        for event in self.events.iter_mut() {
+            let timestamp = (event.frame, event.time_since_startup);
+            f(
+                &mut event.frame,
+                &mut event.time_since_startup,
+                &mut event.input_event,
+            );
+            retimed |= timestamp != (event.frame, event.time_since_startup);
+        }
+
+        if retimed {
+            self.sort_stable();
+        }
     }
 
     /// Sorts the input stream by either [`Time::time_since_startup`] or [`FrameCount`].
@@ -242,9 +791,22 @@ impl TimestampedInputs {
             }
         };
 
+        self.frame_index.invalidate();
         self.events.sort_by(strategy);
     }
 
+    /// Sorts the input stream by [`FrameCount`], and then by time since startup, and resets the `cursor` to 0.
+    ///
+    /// This sort is stable, so events with equal timestamps keep their relative order.
+    /// Use this to make a recording that was assembled or edited by hand replayable again;
+    /// [`TimestampedInputs::validate`] reports whether it is needed.
+    pub fn sort_stable(&mut self) {
+        self.frame_index.invalidate();
+        self.events
+            .sort_by_key(|event| (event.frame, event.time_since_startup));
+        self.reset_cursor();
+    }
+
     /// Is this [`TimestampedInputs`] sorted according to the specified [`SortingStrategy`]?
     pub fn is_sorted(&self, strategy: SortingStrategy) -> bool {
         match strategy {
@@ -313,6 +875,15 @@ impl TimestampedInputs {
         Some(next_read.frame)
     }
 
+    /// The frame of the next event to be played back, if any.
+    ///
+    /// Custom playback loops can use this to skip ahead to the next frame that has events,
+    /// rather than polling every update.
+    #[inline]
+    pub fn next_frame(&self) -> Option<FrameCount> {
+        self.current_framecount()
+    }
+
     /// The time since startup of the last-read event.
     pub fn last_time(&self) -> Option<Duration> {
         if self.cursor == 0 {
@@ -354,17 +925,173 @@ pub enum SortingStrategy {
 
 /// Collects input-relevant events for use in [`TimestampedInputs`]
 // BLOCKED: this should be PartialEq, but we're blocked on https://github.com/bevyengine/bevy/issues/6024
-#[allow(missing_docs)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum InputEvent {
+    /// A key was pressed or released, identified by both its [`KeyCode`] (if any) and scan code
     Keyboard(KeyboardInput),
+    /// A mouse button was pressed or released
     MouseButton(MouseButtonInput),
+    /// The mouse wheel was scrolled, by lines or by pixels
     MouseWheel(MouseWheel),
+    /// The cursor moved to a new position, relative to the window it is in
     CursorMoved(CursorMoved),
+    /// The mouse moved by a relative delta, reported even when the cursor is locked
+    MouseMotion(MouseMotion),
+    /// A touch started, moved, ended or was cancelled
+    Touch(TouchInput),
+    /// A gamepad was connected or disconnected, or one of its buttons or axes changed,
+    /// with the filtered value that Bevy reported
     Gamepad(GamepadEvent),
+    /// A window was resized, to the logical width and height that Bevy reported
+    WindowResized(WindowResized),
+    /// A window gained or lost focus
+    WindowFocused(WindowFocused),
+    /// The app was asked to exit
+    AppExit,
+}
+
+impl InputEvent {
+    /// The keyboard event stored in this event, if it is [`InputEvent::Keyboard`]
+    pub fn as_keyboard(&self) -> Option<&KeyboardInput> {
+        match self {
+            InputEvent::Keyboard(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The mouse button event stored in this event, if it is [`InputEvent::MouseButton`]
+    pub fn as_mouse_button(&self) -> Option<&MouseButtonInput> {
+        match self {
+            InputEvent::MouseButton(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The mouse wheel event stored in this event, if it is [`InputEvent::MouseWheel`]
+    pub fn as_mouse_wheel(&self) -> Option<&MouseWheel> {
+        match self {
+            InputEvent::MouseWheel(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The cursor event stored in this event, if it is [`InputEvent::CursorMoved`]
+    pub fn as_cursor_moved(&self) -> Option<&CursorMoved> {
+        match self {
+            InputEvent::CursorMoved(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The mouse motion event stored in this event, if it is [`InputEvent::MouseMotion`]
+    pub fn as_mouse_motion(&self) -> Option<&MouseMotion> {
+        match self {
+            InputEvent::MouseMotion(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The touch event stored in this event, if it is [`InputEvent::Touch`]
+    pub fn as_touch(&self) -> Option<&TouchInput> {
+        match self {
+            InputEvent::Touch(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The gamepad event stored in this event, if it is [`InputEvent::Gamepad`]
+    pub fn as_gamepad(&self) -> Option<&GamepadEvent> {
+        match self {
+            InputEvent::Gamepad(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The gamepad button event stored in this event, if it is a button change of [`InputEvent::Gamepad`]
+    pub fn as_gamepad_button(&self) -> Option<&GamepadButtonChangedEvent> {
+        match self {
+            InputEvent::Gamepad(GamepadEvent::Button(event)) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The gamepad axis event stored in this event, if it is an axis change of [`InputEvent::Gamepad`]
+    pub fn as_gamepad_axis(&self) -> Option<&GamepadAxisChangedEvent> {
+        match self {
+            InputEvent::Gamepad(GamepadEvent::Axis(event)) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The window resize event stored in this event, if it is [`InputEvent::WindowResized`]
+    pub fn as_window_resized(&self) -> Option<&WindowResized> {
+        match self {
+            InputEvent::WindowResized(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The window focus event stored in this event, if it is [`InputEvent::WindowFocused`]
+    pub fn as_window_focused(&self) -> Option<&WindowFocused> {
+        match self {
+            InputEvent::WindowFocused(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// The [`InputKind`] of this event, ignoring its payload.
+    pub fn kind(&self) -> InputKind {
+        match self {
+            InputEvent::Keyboard(_) => InputKind::Keyboard,
+            InputEvent::MouseButton(_) => InputKind::MouseButton,
+            InputEvent::MouseWheel(_) => InputKind::MouseWheel,
+            InputEvent::CursorMoved(_) => InputKind::CursorMoved,
+            InputEvent::MouseMotion(_) => InputKind::MouseMotion,
+            InputEvent::Touch(_) => InputKind::Touch,
+            InputEvent::Gamepad(_) => InputKind::Gamepad,
+            InputEvent::WindowResized(_) => InputKind::WindowResized,
+            InputEvent::WindowFocused(_) => InputKind::WindowFocused,
+            InputEvent::AppExit => InputKind::AppExit,
+        }
+    }
+}
+
+/// The payload-free category of an [`InputEvent`]
+///
+/// Each variant corresponds to the [`InputEvent`] variant of the same name.
+/// Variants are ordered as they are played back within a single frame:
+/// see [`sort_for_playback`](crate::input_playback::sort_for_playback).
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InputKind {
+    Keyboard,
+    CursorMoved,
+    MouseButton,
+    MouseWheel,
+    MouseMotion,
+    Touch,
+    Gamepad,
+    WindowResized,
+    WindowFocused,
     AppExit,
 }
 
+impl InputKind {
+    /// Every [`InputKind`], in declaration order
+    pub const ALL: [InputKind; 10] = [
+        InputKind::Keyboard,
+        InputKind::CursorMoved,
+        InputKind::MouseButton,
+        InputKind::MouseWheel,
+        InputKind::MouseMotion,
+        InputKind::Touch,
+        InputKind::Gamepad,
+        InputKind::WindowResized,
+        InputKind::WindowFocused,
+        InputKind::AppExit,
+    ];
+}
+
 impl From<KeyboardInput> for InputEvent {
     fn from(event: KeyboardInput) -> Self {
         InputEvent::Keyboard(event)
@@ -389,12 +1116,36 @@ impl From<CursorMoved> for InputEvent {
     }
 }
 
+impl From<MouseMotion> for InputEvent {
+    fn from(event: MouseMotion) -> Self {
+        InputEvent::MouseMotion(event)
+    }
+}
+
+impl From<TouchInput> for InputEvent {
+    fn from(event: TouchInput) -> Self {
+        InputEvent::Touch(event)
+    }
+}
+
 impl From<GamepadEvent> for InputEvent {
     fn from(event: GamepadEvent) -> Self {
         InputEvent::Gamepad(event)
     }
 }
 
+impl From<WindowResized> for InputEvent {
+    fn from(event: WindowResized) -> Self {
+        InputEvent::WindowResized(event)
+    }
+}
+
+impl From<WindowFocused> for InputEvent {
+    fn from(event: WindowFocused) -> Self {
+        InputEvent::WindowFocused(event)
+    }
+}
+
 impl From<AppExit> for InputEvent {
     fn from(_event: AppExit) -> Self {
         InputEvent::AppExit
@@ -417,6 +1168,7 @@ mod tests {
         state: ButtonState::Released,
     });
 
+    #[allow(clippy::useless_conversion)]
     fn complex_timestamped_input() -> TimestampedInputs {
         let mut inputs = TimestampedInputs::default();
         inputs.send(
@@ -448,6 +1200,19 @@ mod tests {
         inputs
     }
 
+    #[test]
+    fn iso_8601_timestamps() {
+        assert_eq!(iso_8601_utc(Duration::ZERO), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            iso_8601_utc(Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00Z"
+        );
+        assert_eq!(
+            iso_8601_utc(Duration::from_millis(1_700_000_000_500)),
+            "2023-11-14T22:13:20Z"
+        );
+    }
+
     #[test]
     fn send_event() {
         let mut timestamped_input = TimestampedInputs::default();
@@ -460,6 +1225,22 @@ mod tests {
     }
 
     #[test]
+    fn send_event_with_source() {
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        timestamped_input.send_with_source(
+            FrameCount(0),
+            Duration::ZERO,
+            LEFT_CLICK_RELEASE,
+            "bot",
+        );
+
+        assert_eq!(timestamped_input.events[0].source, None);
+        assert_eq!(timestamped_input.events[1].source.as_deref(), Some("bot"));
+    }
+
+    #[test]
+    #[allow(clippy::useless_conversion)]
     fn send_multiple_events() {
         let mut timestamped_input = TimestampedInputs::default();
         let events = [LEFT_CLICK_PRESS, LEFT_CLICK_RELEASE];
@@ -487,6 +1268,290 @@ mod tests {
         // assert_eq!(timestamped_input.current_input(), Some(LEFT_CLICK_RELEASE));
     }
 
+    #[test]
+    fn get_event() {
+        let timestamped_input = complex_timestamped_input();
+        assert_eq!(timestamped_input.get(1).unwrap().frame, FrameCount(1));
+        assert_eq!(timestamped_input.get(4).unwrap().frame, FrameCount(3));
+        assert!(timestamped_input.get(5).is_none());
+        assert_eq!(timestamped_input.cursor, 0);
+    }
+
+    #[test]
+    fn next_frame() {
+        let mut timestamped_input = complex_timestamped_input();
+        assert_eq!(timestamped_input.next_frame(), Some(FrameCount(0)));
+
+        let _ = timestamped_input.iter_until_frame(FrameCount(1));
+        assert_eq!(timestamped_input.next_frame(), Some(FrameCount(2)));
+
+        let _ = timestamped_input.iter_rest();
+        assert_eq!(timestamped_input.next_frame(), None);
+    }
+
+    #[test]
+    fn insert_event() {
+        let mut timestamped_input = complex_timestamped_input();
+        timestamped_input.cursor = 3;
+
+        timestamped_input.insert(
+            LEFT_CLICK_RELEASE,
+            FrameCount(1),
+            Duration::from_millis(1500),
+        );
+        assert_eq!(timestamped_input.len(), 6);
+        assert_eq!(timestamped_input.events[2].input_event, LEFT_CLICK_RELEASE);
+        assert_eq!(timestamped_input.events[2].frame, FrameCount(1));
+        assert!(TimestampedInputs::is_sorted(
+            &timestamped_input,
+            SortingStrategy::TimeSinceStartup
+        ));
+        assert!(TimestampedInputs::is_sorted(
+            &timestamped_input,
+            SortingStrategy::FrameCount
+        ));
+
+        // The cursor still points at the same event
+        assert_eq!(timestamped_input.cursor, 4);
+        assert_eq!(
+            timestamped_input.current_time(),
+            Some(Duration::from_secs(3))
+        );
+    }
+
+    #[test]
+    fn duration_and_frame_len() {
+        let empty = TimestampedInputs::default();
+        assert_eq!(empty.duration(), Duration::ZERO);
+        assert_eq!(empty.frame_len(), FrameCount(0));
+
+        let timestamped_input = complex_timestamped_input();
+        assert_eq!(timestamped_input.duration(), Duration::from_secs(3));
+        assert_eq!(timestamped_input.frame_len(), FrameCount(3));
+    }
+
+    #[test]
+    fn append() {
+        let mut first = TimestampedInputs::default();
+        first.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        first.send(FrameCount(2), Duration::from_secs(2), LEFT_CLICK_RELEASE);
+        let second = first.clone();
+
+        let mut combined = TimestampedInputs::default();
+        combined.append(&first);
+        assert_eq!(combined, first);
+
+        combined.append(&second);
+        assert_eq!(combined.len(), 4);
+        let frames: Vec<_> = combined.events.iter().map(|event| event.frame).collect();
+        assert_eq!(
+            frames,
+            [FrameCount(1), FrameCount(2), FrameCount(3), FrameCount(4)]
+        );
+        assert_eq!(
+            combined.time_range(),
+            Some((Duration::from_secs(1), Duration::from_secs(4)))
+        );
+
+        // Recordings that begin on frame 0 still begin after the last event
+        let mut from_zero = TimestampedInputs::default();
+        from_zero.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        from_zero.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_RELEASE);
+        let mut combined = first.clone();
+        combined.append(&from_zero);
+        let timestamps: Vec<_> = combined
+            .events
+            .iter()
+            .map(|event| (event.frame.0, event.time_since_startup.as_secs()))
+            .collect();
+        assert_eq!(timestamps, [(1, 1), (2, 2), (3, 3), (4, 4)]);
+
+        // And a recording that begins late does not keep its leading gap
+        let mut late = TimestampedInputs::default();
+        late.send(FrameCount(10), Duration::from_secs(10), LEFT_CLICK_PRESS);
+        let mut combined = first.clone();
+        combined.append(&late);
+        assert_eq!(combined.events[2].frame, FrameCount(3));
+        assert_eq!(
+            combined.events[2].time_since_startup,
+            Duration::from_secs(3)
+        );
+    }
+
+    #[test]
+    fn sort_stable() {
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(2), Duration::from_secs(2), LEFT_CLICK_PRESS);
+        inputs.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_RELEASE);
+        inputs.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        inputs.send(FrameCount(1), Duration::ZERO, InputEvent::AppExit);
+        inputs.cursor = 2;
+        assert!(inputs.validate().is_err());
+
+        inputs.sort_stable();
+        assert_eq!(inputs.validate(), Ok(()));
+        assert_eq!(inputs.cursor, 0);
+        let sorted: Vec<_> = inputs
+            .events
+            .iter()
+            .map(|event| (event.frame, event.input_event.clone()))
+            .collect();
+        // Events with equal timestamps keep their relative order
+        assert_eq!(
+            sorted,
+            [
+                (FrameCount(1), InputEvent::AppExit),
+                (FrameCount(1), LEFT_CLICK_RELEASE),
+                (FrameCount(1), LEFT_CLICK_PRESS),
+                (FrameCount(2), LEFT_CLICK_PRESS),
+            ]
+        );
+    }
+
+    #[test]
+    fn for_each_mut() {
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        inputs.send(FrameCount(2), Duration::from_secs(2), LEFT_CLICK_RELEASE);
+        inputs.send(FrameCount(3), Duration::from_secs(3), InputEvent::AppExit);
+        inputs.cursor = 1;
+
+        // Editing only the events leaves the cursor alone
+        inputs.for_each_mut(|_, _, event| {
+            if *event == LEFT_CLICK_RELEASE {
+                *event = LEFT_CLICK_PRESS;
+            }
+        });
+        assert_eq!(inputs.cursor, 1);
+        assert_eq!(inputs.events[1].input_event, LEFT_CLICK_PRESS);
+
+        inputs.for_each_mut(|_, time, _| *time *= 2);
+        assert_eq!(inputs.validate(), Ok(()));
+        assert_eq!(inputs.cursor, 0);
+        let times: Vec<_> = inputs
+            .events
+            .iter()
+            .map(|event| event.time_since_startup)
+            .collect();
+        assert_eq!(
+            times,
+            [
+                Duration::from_secs(2),
+                Duration::from_secs(4),
+                Duration::from_secs(6)
+            ]
+        );
+
+        // Moving an event earlier re-sorts the recording
+        inputs.for_each_mut(|frame, time, event| {
+            if *event == InputEvent::AppExit {
+                *frame = FrameCount(0);
+                *time = Duration::ZERO;
+            }
+        });
+        assert_eq!(inputs.validate(), Ok(()));
+        assert_eq!(inputs.events[0].input_event, InputEvent::AppExit);
+        assert_eq!(inputs.frame_range(), Some((FrameCount(0), FrameCount(2))));
+    }
+
+    #[test]
+    fn merge() {
+        let mut keyboard = TimestampedInputs::default();
+        let key_press = InputEvent::Keyboard(KeyboardInput {
+            scan_code: 1,
+            key_code: Some(KeyCode::F),
+            state: ButtonState::Pressed,
+        });
+        keyboard.send(FrameCount(1), Duration::from_secs(1), key_press.clone());
+        keyboard.send(FrameCount(3), Duration::from_secs(3), key_press.clone());
+        keyboard.cursor = 1;
+
+        let mut mouse = TimestampedInputs::default();
+        mouse.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        mouse.send(FrameCount(2), Duration::from_secs(2), LEFT_CLICK_RELEASE);
+
+        keyboard.merge(&mouse);
+        assert_eq!(keyboard.cursor, 0);
+        let merged: Vec<_> = keyboard
+            .events
+            .iter()
+            .map(|event| (event.frame, event.input_event.clone()))
+            .collect();
+        assert_eq!(
+            merged,
+            [
+                (FrameCount(1), key_press.clone()),
+                (FrameCount(1), LEFT_CLICK_PRESS),
+                (FrameCount(2), LEFT_CLICK_RELEASE),
+                (FrameCount(3), key_press),
+            ]
+        );
+    }
+
+    #[test]
+    fn seek_to_frame() {
+        let mut timestamped_input = complex_timestamped_input();
+
+        assert_eq!(timestamped_input.seek_to_frame(FrameCount(2)), 2);
+        assert_eq!(timestamped_input.current_framecount(), Some(FrameCount(2)));
+
+        // Seeking backwards is allowed
+        assert_eq!(timestamped_input.seek_to_frame(FrameCount(1)), 1);
+
+        // Out of range frames clamp to the start or end
+        assert_eq!(timestamped_input.seek_to_frame(FrameCount(0)), 0);
+        assert_eq!(timestamped_input.seek_to_frame(FrameCount(100)), 5);
+        assert_eq!(timestamped_input.current_framecount(), None);
+
+        // And normal iteration resumes from the new cursor
+        timestamped_input.seek_to_frame(FrameCount(3));
+        assert_eq!(timestamped_input.iter_rest().into_iter().count(), 1);
+    }
+
+    #[test]
+    fn seek_to_duration() {
+        let mut timestamped_input = complex_timestamped_input();
+
+        assert_eq!(
+            timestamped_input.seek_to_duration(Duration::from_secs(3)),
+            3
+        );
+        assert_eq!(
+            timestamped_input.current_time(),
+            Some(Duration::from_secs(3))
+        );
+
+        // Times between events seek to the next event
+        assert_eq!(
+            timestamped_input.seek_to_duration(Duration::from_millis(500)),
+            1
+        );
+
+        assert_eq!(timestamped_input.seek_to_duration(Duration::ZERO), 0);
+        assert_eq!(
+            timestamped_input.seek_to_duration(Duration::from_secs(100)),
+            5
+        );
+    }
+
+    #[test]
+    fn progress() {
+        assert_eq!(TimestampedInputs::default().progress(), 0.0);
+        assert_eq!(TimestampedInputs::default().remaining(), 0);
+
+        let mut timestamped_input = complex_timestamped_input();
+        assert_eq!(timestamped_input.progress(), 0.0);
+        assert_eq!(timestamped_input.remaining(), 5);
+
+        let _ = timestamped_input.iter_until_frame(FrameCount(1));
+        assert_eq!(timestamped_input.progress(), 0.4);
+        assert_eq!(timestamped_input.remaining(), 3);
+
+        let _ = timestamped_input.iter_rest();
+        assert_eq!(timestamped_input.progress(), 1.0);
+        assert_eq!(timestamped_input.remaining(), 0);
+    }
+
     /// Tests to verify that none of the iteration methods consume events
     #[test]
     fn repeated_iteration() {
@@ -599,6 +1664,291 @@ mod tests {
         assert_eq!(iter.into_iter().count(), 3);
     }
 
+    #[test]
+    fn seeking_large_recordings() {
+        let mut inputs = TimestampedInputs::default();
+        for i in 0..50_000 {
+            // Several events per frame, with some frames skipped entirely
+            let frame = FrameCount(i / 3 + i / 1000);
+            inputs.send(frame, Duration::from_millis(frame.0 * 16), LEFT_CLICK_PRESS);
+        }
+
+        for target in [0, 1, 9_999, 10_000, 16_699, 16_700, 17_000, 100_000] {
+            let frame = FrameCount(target);
+            let expected = inputs
+                .events
+                .iter()
+                .position(|event| event.frame >= frame)
+                .unwrap_or(inputs.len());
+            assert_eq!(inputs.seek_to_frame(frame), expected);
+
+            let time = Duration::from_millis(target * 16 + 1);
+            let expected = inputs
+                .events
+                .iter()
+                .position(|event| event.time_since_startup >= time)
+                .unwrap_or(inputs.len());
+            assert_eq!(inputs.seek_to_duration(time), expected);
+        }
+
+        inputs.seek_to_frame(FrameCount(100));
+        let played: Vec<_> = inputs
+            .iter_between_frames(FrameCount(200), FrameCount(300))
+            .into_iter()
+            .collect();
+        assert!(played
+            .iter()
+            .all(|event| (200..300).contains(&event.frame.0)));
+        assert_eq!(played.len(), 300);
+        assert_eq!(inputs.current_framecount(), Some(FrameCount(300)));
+    }
+
+    #[test]
+    fn events_in_range() {
+        let mut inputs = complex_timestamped_input();
+        inputs.cursor = 1;
+
+        let frames: Vec<_> = inputs
+            .events_in_range(FrameCount(1), FrameCount(3))
+            .map(|event| event.frame)
+            .collect();
+        assert_eq!(frames, [FrameCount(1), FrameCount(2), FrameCount(2)]);
+        assert_eq!(inputs.cursor, 1);
+
+        assert_eq!(
+            inputs.events_in_range(FrameCount(3), FrameCount(1)).count(),
+            0
+        );
+        assert_eq!(
+            inputs
+                .events_in_range(FrameCount(9), FrameCount(10))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn clear_keeps_capacity() {
+        let mut inputs = complex_timestamped_input();
+        inputs.cursor = 2;
+        let capacity = inputs.events.capacity();
+
+        inputs.clear();
+        assert!(inputs.is_empty());
+        assert_eq!(inputs.cursor, 0);
+        assert_eq!(inputs.events.capacity(), capacity);
+    }
+
+    #[test]
+    fn rebase() {
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(FrameCount(10), Duration::from_secs(5), LEFT_CLICK_PRESS);
+        inputs.send(FrameCount(12), Duration::from_secs(7), LEFT_CLICK_RELEASE);
+
+        inputs.rebase();
+        assert_eq!(inputs.events[0].frame, FrameCount(0));
+        assert_eq!(inputs.events[0].time_since_startup, Duration::ZERO);
+        assert_eq!(inputs.events[1].frame, FrameCount(2));
+        assert_eq!(inputs.events[1].time_since_startup, Duration::from_secs(2));
+
+        let mut empty = TimestampedInputs::default();
+        empty.rebase();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn dedup_axes() {
+        use bevy::input::gamepad::{Gamepad, GamepadAxisChangedEvent, GamepadAxisType};
+
+        let axis_event = |axis_type, value| {
+            InputEvent::Gamepad(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                Gamepad::new(0),
+                axis_type,
+                value,
+            )))
+        };
+
+        let mut inputs = TimestampedInputs::default();
+        // A slow ramp of the left stick, interleaved with a single right stick event
+        for step in 0..100 {
+            let value = step as f32 * 0.001;
+            inputs.send(
+                FrameCount(step),
+                Duration::ZERO,
+                axis_event(GamepadAxisType::LeftStickX, value),
+            );
+        }
+        inputs.send(
+            FrameCount(100),
+            Duration::ZERO,
+            axis_event(GamepadAxisType::RightStickX, 0.0),
+        );
+        inputs.cursor = 50;
+
+        inputs.dedup_axes(0.01);
+        // Only around one in ten left stick values differs enough from the last kept value
+        assert_eq!(inputs.len(), 11);
+        assert_eq!(inputs.cursor, 0);
+    }
+
+    #[test]
+    fn recalibrate_axes() {
+        use bevy::input::gamepad::Gamepad;
+
+        let axis_event = |axis_type, value| {
+            InputEvent::Gamepad(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                Gamepad::new(0),
+                axis_type,
+                value,
+            )))
+        };
+
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(
+            FrameCount(0),
+            Duration::ZERO,
+            axis_event(GamepadAxisType::LeftStickX, 0.1),
+        );
+        inputs.send(
+            FrameCount(1),
+            Duration::ZERO,
+            axis_event(GamepadAxisType::LeftStickX, -0.95),
+        );
+        inputs.send(
+            FrameCount(1),
+            Duration::ZERO,
+            axis_event(GamepadAxisType::LeftStickY, 0.5),
+        );
+        inputs.send(FrameCount(2), Duration::ZERO, LEFT_CLICK_PRESS);
+
+        inputs.recalibrate_axes([(GamepadAxisType::LeftStickX, 0.1)].into_iter().collect());
+        let values: Vec<_> = inputs
+            .events
+            .iter()
+            .filter_map(|event| event.input_event.as_gamepad_axis())
+            .map(|event| event.value)
+            .collect();
+        // Corrected values are clamped, and other axes are untouched
+        assert_eq!(values, [0.0, -1.0, 0.5]);
+        assert_eq!(inputs.events[3].input_event, LEFT_CLICK_PRESS);
+    }
+
+    #[test]
+    fn resample() {
+        let mut inputs = complex_timestamped_input();
+        inputs.resample(144.0, 72.0);
+
+        let frames: Vec<_> = inputs.events.iter().map(|event| event.frame).collect();
+        assert_eq!(
+            frames,
+            [
+                FrameCount(0),
+                FrameCount(0),
+                FrameCount(1),
+                FrameCount(1),
+                FrameCount(1)
+            ]
+        );
+        // Events on the same frame keep their recorded order
+        assert_eq!(inputs.events[1].input_event, LEFT_CLICK_RELEASE);
+        assert_eq!(inputs.events[4].time_since_startup, Duration::from_secs(3));
+
+        inputs.resample(0.0, 60.0);
+        assert_eq!(inputs.events[4].frame, FrameCount(1));
+    }
+
+    #[test]
+    fn input_event_accessors() {
+        use bevy::input::gamepad::{Gamepad, GamepadButtonType};
+
+        assert!(matches!(
+            LEFT_CLICK_PRESS.as_mouse_button(),
+            Some(MouseButtonInput {
+                button: MouseButton::Left,
+                ..
+            })
+        ));
+        assert!(LEFT_CLICK_PRESS.as_keyboard().is_none());
+        assert!(InputEvent::AppExit.as_mouse_button().is_none());
+
+        let button = InputEvent::Gamepad(GamepadEvent::Button(GamepadButtonChangedEvent::new(
+            Gamepad::new(0),
+            GamepadButtonType::South,
+            1.0,
+        )));
+        assert!(button.as_gamepad().is_some());
+        assert_eq!(
+            button.as_gamepad_button().map(|event| event.value),
+            Some(1.0)
+        );
+        assert!(button.as_gamepad_axis().is_none());
+    }
+
+    #[test]
+    fn duration_only_round_trip() {
+        // Recorded at 60 frames per second, with times partway through each frame
+        let mut inputs = TimestampedInputs::default();
+        for frame in [0, 1, 5, 59, 60, 120] {
+            let time = Duration::from_secs_f64((frame as f64 + 0.5) / 60.0);
+            inputs.send(FrameCount(frame), time, LEFT_CLICK_PRESS);
+        }
+
+        let durations = inputs.to_duration_only();
+        assert_eq!(durations.len(), 6);
+        assert_eq!(durations[2].0, inputs.events[2].time_since_startup);
+
+        let round_tripped = TimestampedInputs::from_durations(durations, 60.0);
+        for (original, round_tripped) in inputs.events.iter().zip(&round_tripped.events) {
+            assert!(original.frame.0.abs_diff(round_tripped.frame.0) <= 1);
+            assert_eq!(
+                original.time_since_startup,
+                round_tripped.time_since_startup
+            );
+            assert_eq!(original.input_event, round_tripped.input_event);
+        }
+
+        let invalid = TimestampedInputs::from_durations(inputs.to_duration_only(), -1.0);
+        assert_eq!(invalid.frame_range(), Some((FrameCount(0), FrameCount(0))));
+    }
+
+    #[test]
+    fn truncate_at_cursor() {
+        let mut inputs = complex_timestamped_input();
+        inputs.seek_to_frame(FrameCount(2));
+
+        inputs.truncate_at_cursor();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs.cursor, 2);
+        assert_eq!(inputs.last_framecount(), Some(FrameCount(1)));
+
+        // A cursor past the end leaves every event in place
+        let mut inputs = complex_timestamped_input();
+        inputs.cursor = 10;
+        inputs.truncate_at_cursor();
+        assert_eq!(inputs.len(), 5);
+        assert_eq!(inputs.cursor, 5);
+    }
+
+    #[test]
+    fn frame_index_invalidation() {
+        let mut inputs = complex_timestamped_input();
+        inputs.build_index();
+        assert_eq!(inputs.seek_to_frame(FrameCount(3)), 4);
+
+        inputs.insert(LEFT_CLICK_RELEASE, FrameCount(1), Duration::from_secs(1));
+        assert_eq!(inputs.seek_to_frame(FrameCount(3)), 5);
+
+        inputs.events.remove(0);
+        inputs.invalidate_index();
+        assert_eq!(inputs.seek_to_frame(FrameCount(3)), 4);
+        assert_eq!(inputs.seek_to_frame(FrameCount(4)), inputs.len());
+
+        // Indexing never changes equality
+        let mut unindexed = inputs.clone();
+        unindexed.invalidate_index();
+        assert_eq!(inputs, unindexed);
+    }
+
     #[test]
     fn iter_between_times() {
         let mut timestamped_input = complex_timestamped_input();
@@ -0,0 +1,72 @@
+#![cfg(feature = "leafwing-input-manager")]
+
+use bevy::prelude::*;
+
+use leafwing_input_manager::action_state::ActionState;
+use leafwing_input_manager::Actionlike;
+use leafwing_input_playback::action_state::{
+    ActionStatePlugin, ActionStateRecording, CaptureActionState, PlaybackActionState,
+};
+use leafwing_input_playback::frame_counting::FrameCount;
+
+#[derive(Actionlike, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+    Run,
+}
+
+fn action_state_app() -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(ActionStatePlugin::<Action>::default());
+
+    app
+}
+
+#[test]
+fn capture_and_playback_action_state() {
+    let mut app = action_state_app();
+    let player = app
+        .world
+        .spawn((ActionState::<Action>::default(), CaptureActionState))
+        .id();
+
+    // Jump on frame 2, and run on frames 3 and 4
+    app.update();
+    let mut action_state = app.world.get_mut::<ActionState<Action>>(player).unwrap();
+    action_state.press(Action::Jump);
+    app.update();
+    let mut action_state = app.world.get_mut::<ActionState<Action>>(player).unwrap();
+    action_state.release(Action::Jump);
+    action_state.press(Action::Run);
+    app.update();
+    app.update();
+    app.world.entity_mut(player).remove::<CaptureActionState>();
+
+    let recording = app.world.resource::<ActionStateRecording<Action>>();
+    assert_eq!(recording.snapshots.len(), 4);
+    assert_eq!(recording.snapshots[1].frame, FrameCount(2));
+
+    let ghost = app
+        .world
+        .spawn((
+            ActionState::<Action>::default(),
+            PlaybackActionState::new(FrameCount(2), FrameCount(4)),
+        ))
+        .id();
+
+    let mut played = Vec::new();
+    for _ in 0..3 {
+        app.update();
+        let action_state = app.world.get::<ActionState<Action>>(ghost).unwrap();
+        played.push((
+            action_state.pressed(Action::Jump),
+            action_state.pressed(Action::Run),
+        ));
+    }
+
+    // Frame 4 is excluded, so the final state of the range is held
+    assert_eq!(played, [(true, false), (false, true), (false, true)]);
+    assert!(app.world.get::<PlaybackActionState>(ghost).is_none());
+}
@@ -0,0 +1,93 @@
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+
+use bevy::window::WindowPlugin;
+use leafwing_input_playback::hotkeys::{RecordingHotkeys, RecordingHotkeysPlugin};
+use leafwing_input_playback::input_capture::{InputCapturePlugin, InputModesCaptured};
+use leafwing_input_playback::input_playback::{InputPlaybackPlugin, PlaybackStrategy};
+use leafwing_input_playback::timestamped_input::{InputEvent, TimestampedInputs};
+
+fn hotkeys_app() -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(InputPlugin)
+        .add_plugin(InputCapturePlugin)
+        .add_plugin(InputPlaybackPlugin)
+        .add_plugin(RecordingHotkeysPlugin)
+        .insert_resource(InputModesCaptured::DISABLE_ALL)
+        .insert_resource(PlaybackStrategy::Paused);
+
+    app
+}
+
+fn press(key_code: KeyCode) -> KeyboardInput {
+    KeyboardInput {
+        scan_code: 0,
+        key_code: Some(key_code),
+        state: ButtonState::Pressed,
+    }
+}
+
+fn release(key_code: KeyCode) -> KeyboardInput {
+    KeyboardInput {
+        state: ButtonState::Released,
+        ..press(key_code)
+    }
+}
+
+#[test]
+fn hotkeys_toggle_recording_and_playback() {
+    let mut app = hotkeys_app();
+    let hotkeys = *app.world.resource::<RecordingHotkeys>();
+
+    // Stale input is discarded when a fresh recording starts
+    app.world.resource_mut::<TimestampedInputs>().send(
+        Default::default(),
+        Default::default(),
+        press(KeyCode::Q).into(),
+    );
+    app.world.send_event(press(hotkeys.record));
+    app.update();
+    assert_eq!(
+        *app.world.resource::<InputModesCaptured>(),
+        InputModesCaptured::ENABLE_ALL
+    );
+
+    app.world.send_event(release(hotkeys.record));
+    app.world.send_event(press(KeyCode::F));
+    app.update();
+
+    app.world.send_event(press(hotkeys.play));
+    app.update();
+    assert_eq!(
+        *app.world.resource::<InputModesCaptured>(),
+        InputModesCaptured::DISABLE_ALL
+    );
+    assert!(matches!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::FrameRangeOnce(..)
+    ));
+
+    // Only the input between the hotkeys was recorded
+    let recorded: Vec<_> = app
+        .world
+        .resource::<TimestampedInputs>()
+        .events
+        .iter()
+        .map(|event| event.input_event.clone())
+        .collect();
+    assert_eq!(recorded, [InputEvent::Keyboard(press(KeyCode::F))]);
+
+    app.world.send_event(release(hotkeys.play));
+    app.update();
+    app.world.send_event(press(hotkeys.stop));
+    app.update();
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
@@ -1,14 +1,22 @@
+use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadEvent};
 use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::MouseButtonInput;
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel};
 use bevy::input::ButtonState;
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
 
-use bevy::window::WindowPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use bevy::window::{WindowFocused, WindowPlugin, WindowResized};
 use leafwing_input_playback::frame_counting::FrameCount;
-use leafwing_input_playback::input_capture::{InputCapturePlugin, InputModesCaptured};
+use leafwing_input_playback::input_capture::{
+    AxisPrecision, CaptureCapacityHint, CaptureFrameLimit, CaptureOnlyTransitions,
+    CapturePassthrough, CaptureRealTime, CaptureSet, CaptureStartDelay, CaptureStarted,
+    CaptureStopped, GamepadFilter, InputCapturePlugin, InputModesCaptured, KeyFilter,
+};
+use leafwing_input_playback::serde::SaveRecording;
 use leafwing_input_playback::timestamped_input::{
-    InputEvent, TimestampedInputEvent, TimestampedInputs,
+    InputEvent, TimestampedInputEvent, TimestampedInputs, OS_SOURCE,
 };
 
 const TEST_PRESS: KeyboardInput = KeyboardInput {
@@ -39,6 +47,18 @@ fn capture_app() -> App {
     app
 }
 
+/// The frame of the last captured event that came from an input device.
+///
+/// `AppExit` events are always captured, and so are ignored.
+fn last_device_input_frame(timestamped_input: &TimestampedInputs) -> Option<FrameCount> {
+    timestamped_input
+        .events
+        .iter()
+        .rev()
+        .find(|event| !matches!(event.input_event, InputEvent::AppExit))
+        .map(|event| event.frame)
+}
+
 #[test]
 fn capture_sent_events() {
     let mut app = capture_app();
@@ -153,3 +173,538 @@ fn toggle_input_capture() {
     let timestamped_input = app.world.resource::<TimestampedInputs>();
     assert_eq!(timestamped_input.len(), 3);
 }
+
+#[test]
+#[cfg(feature = "testing")]
+fn no_input_captured_after_disabling() {
+    use leafwing_input_playback::testing::assert_no_input_after;
+
+    let mut app = capture_app();
+
+    let mut keyboard_events = app.world.resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    app.update();
+
+    app.insert_resource(InputModesCaptured::DISABLE_ALL);
+    let disabled_frame = *app.world.resource::<FrameCount>();
+
+    // Stragglers sent after capture was disabled, across several frames
+    for _ in 0..3 {
+        let mut keyboard_events = app.world.resource_mut::<Events<KeyboardInput>>();
+        keyboard_events.send(TEST_RELEASE);
+        let mut mouse_events = app.world.resource_mut::<Events<MouseButtonInput>>();
+        mouse_events.send(TEST_MOUSE);
+        app.update();
+    }
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+    assert_no_input_after(timestamped_input, disabled_frame);
+}
+
+#[test]
+fn reduced_axis_precision() {
+    let mut app = capture_app();
+    app.insert_resource(AxisPrecision::Bits(8));
+
+    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEvent>>();
+    gamepad_events.send(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+        Gamepad::new(0),
+        GamepadAxisType::LeftStickX,
+        0.123_456,
+    )));
+    gamepad_events.send(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+        Gamepad::new(0),
+        GamepadAxisType::LeftStickY,
+        -1.0,
+    )));
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    let values: Vec<f32> = timestamped_input
+        .events
+        .iter()
+        .filter_map(|event| match &event.input_event {
+            InputEvent::Gamepad(GamepadEvent::Axis(axis_event)) => Some(axis_event.value),
+            _ => None,
+        })
+        .collect();
+
+    // 0.123456 is rounded to the nearest multiple of 1/128
+    assert_eq!(values, vec![16.0 / 128.0, -1.0]);
+}
+
+#[test]
+fn captured_events_are_labelled_os() {
+    let mut app = capture_app();
+
+    let mut keyboard_events = app.world.resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    app.update();
+
+    // Events injected by other systems can carry their own label
+    let mut timestamped_input = app.world.resource_mut::<TimestampedInputs>();
+    timestamped_input.send_with_source(
+        FrameCount(2),
+        Duration::ZERO,
+        TEST_RELEASE.into(),
+        "synthetic",
+    );
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(
+        timestamped_input.events[0].source.as_deref(),
+        Some(OS_SOURCE)
+    );
+    assert_eq!(
+        timestamped_input.events[1].source.as_deref(),
+        Some("synthetic")
+    );
+}
+
+#[test]
+fn save_recording_on_demand() {
+    let mut app = capture_app();
+    let path = std::env::temp_dir().join("leafwing_input_playback_save_recording.ron");
+
+    let mut keyboard_events = app.world.resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    keyboard_events.send(TEST_RELEASE);
+    app.update();
+
+    // Partially play back the recording, to check that the cursor is not saved
+    app.world.resource_mut::<TimestampedInputs>().cursor = 1;
+    app.world.send_event(SaveRecording(path.clone()));
+    app.update();
+
+    let saved: TimestampedInputs = ron::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut expected = app.world.resource::<TimestampedInputs>().clone();
+    expected.cursor = 0;
+    assert_eq!(saved, expected);
+}
+
+#[test]
+fn capture_mouse_deltas() {
+    let mut app = capture_app();
+    let motion = MouseMotion {
+        delta: Vec2::new(3.5, -2.0),
+    };
+
+    app.world.send_event(motion);
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+    assert_eq!(
+        timestamped_input.events[0].input_event,
+        InputEvent::MouseMotion(motion)
+    );
+
+    // Mouse deltas can be toggled independently
+    app.insert_resource(InputModesCaptured {
+        mouse_deltas: false,
+        ..Default::default()
+    });
+    app.world.send_event(motion);
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+}
+
+#[test]
+fn capture_window_events() {
+    let mut app = capture_app();
+    let window = Entity::from_raw(7);
+    let resized = WindowResized {
+        window,
+        width: 800.0,
+        height: 600.0,
+    };
+    let focused = WindowFocused {
+        window,
+        focused: false,
+    };
+
+    app.world.send_event(resized.clone());
+    app.world.send_event(focused.clone());
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    let captured: Vec<_> = timestamped_input
+        .events
+        .iter()
+        .map(|event| event.input_event.clone())
+        .collect();
+    assert_eq!(
+        captured,
+        [
+            InputEvent::WindowResized(resized.clone()),
+            InputEvent::WindowFocused(focused)
+        ]
+    );
+
+    // Window events can be toggled independently
+    app.insert_resource(InputModesCaptured {
+        window: false,
+        ..Default::default()
+    });
+    app.world.send_event(resized);
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 2);
+}
+
+#[test]
+fn capture_mouse_wheel() {
+    let mut app = capture_app();
+    let scrolls = [
+        MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: -3.0,
+        },
+        MouseWheel {
+            unit: MouseScrollUnit::Pixel,
+            x: 12.5,
+            y: 0.125,
+        },
+    ];
+
+    for scroll in scrolls {
+        app.world.send_event(scroll);
+    }
+    app.update();
+
+    // Units and amounts are preserved exactly
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    let captured: Vec<_> = timestamped_input
+        .events
+        .iter()
+        .map(|event| event.input_event.clone())
+        .collect();
+    assert_eq!(captured, scrolls.map(InputEvent::MouseWheel));
+
+    // The mouse wheel can be toggled independently of mouse buttons
+    app.insert_resource(InputModesCaptured {
+        mouse_wheel: false,
+        ..Default::default()
+    });
+    app.world.send_event(scrolls[0]);
+    app.world.send_event(TEST_MOUSE);
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 3);
+    assert!(matches!(
+        timestamped_input.events[2].input_event,
+        InputEvent::MouseButton(_)
+    ));
+}
+
+#[test]
+fn key_filter() {
+    let w = KeyboardInput {
+        scan_code: 17,
+        key_code: Some(KeyCode::W),
+        state: ButtonState::Pressed,
+    };
+
+    // Everything is captured by default
+    let mut app = capture_app();
+    app.world.send_event(w);
+    app.world.send_event(TEST_PRESS);
+    app.update();
+    assert_eq!(app.world.resource::<TimestampedInputs>().len(), 2);
+
+    let mut app = capture_app();
+    app.insert_resource(KeyFilter::Allow([KeyCode::W].into_iter().collect()));
+    app.world.send_event(w);
+    app.world.send_event(TEST_PRESS);
+    app.update();
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+    assert_eq!(
+        timestamped_input.events[0].input_event,
+        InputEvent::Keyboard(w)
+    );
+
+    let mut app = capture_app();
+    app.insert_resource(KeyFilter::Deny([KeyCode::W].into_iter().collect()));
+    app.world.send_event(w);
+    app.world.send_event(TEST_PRESS);
+    app.update();
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+    assert_eq!(
+        timestamped_input.events[0].input_event,
+        InputEvent::Keyboard(TEST_PRESS)
+    );
+}
+
+#[test]
+fn capture_only_transitions() {
+    let mut app = capture_app();
+    app.insert_resource(CaptureOnlyTransitions(true));
+
+    // Holding a key repeats its press
+    app.world.send_event(TEST_PRESS);
+    app.update();
+    for _ in 0..3 {
+        app.world.send_event(TEST_PRESS);
+        app.update();
+    }
+    app.world.send_event(TEST_RELEASE);
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    let captured: Vec<_> = timestamped_input
+        .events
+        .iter()
+        .map(|event| event.input_event.clone())
+        .collect();
+    assert_eq!(
+        captured,
+        [
+            InputEvent::Keyboard(TEST_PRESS),
+            InputEvent::Keyboard(TEST_RELEASE)
+        ]
+    );
+
+    // Once released, the key can be pressed again
+    app.world.send_event(TEST_PRESS);
+    app.update();
+    assert_eq!(app.world.resource::<TimestampedInputs>().len(), 3);
+
+    // Releases are tracked even while capture is disabled,
+    // so the next press is not mistaken for a repeat
+    *app.world.resource_mut::<InputModesCaptured>() = InputModesCaptured::DISABLE_ALL;
+    app.world.send_event(TEST_RELEASE);
+    app.update();
+    *app.world.resource_mut::<InputModesCaptured>() = InputModesCaptured::ENABLE_ALL;
+    app.world.send_event(TEST_PRESS);
+    app.update();
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 4);
+    assert_eq!(
+        timestamped_input.events[3].input_event,
+        InputEvent::Keyboard(TEST_PRESS)
+    );
+
+    // Repeats are recorded by default
+    let mut app = capture_app();
+    app.world.send_event(TEST_PRESS);
+    app.world.send_event(TEST_PRESS);
+    app.update();
+    assert_eq!(app.world.resource::<TimestampedInputs>().len(), 2);
+}
+
+#[test]
+fn capture_real_time() {
+    for real_time in [false, true] {
+        let mut app = capture_app();
+        app.insert_resource(CaptureRealTime(real_time));
+        // The app's clock never advances
+        let startup = app.world.resource::<Time>().startup();
+        app.insert_resource(TimeUpdateStrategy::ManualInstant(startup));
+
+        std::thread::sleep(Duration::from_millis(10));
+        app.world.send_event(TEST_PRESS);
+        app.update();
+
+        let timestamped_input = app.world.resource::<TimestampedInputs>();
+        let time_since_startup = timestamped_input.events[0].time_since_startup;
+        if real_time {
+            assert!(time_since_startup >= Duration::from_millis(10));
+        } else {
+            assert_eq!(time_since_startup, Duration::ZERO);
+        }
+    }
+}
+
+#[test]
+fn gamepad_filter() {
+    let player_one = Gamepad::new(0);
+    let player_two = Gamepad::new(1);
+
+    let mut app = capture_app();
+    app.insert_resource(GamepadFilter::Only(player_one));
+    for gamepad in [player_one, player_two] {
+        app.world
+            .send_event(GamepadEvent::Button(GamepadButtonChangedEvent::new(
+                gamepad,
+                GamepadButtonType::South,
+                1.0,
+            )));
+    }
+    app.update();
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+    assert!(matches!(
+        timestamped_input.events[0].input_event,
+        InputEvent::Gamepad(GamepadEvent::Button(GamepadButtonChangedEvent { gamepad, .. }))
+            if gamepad == player_one
+    ));
+}
+
+#[test]
+fn capture_capacity_hint() {
+    let mut app = capture_app();
+    app.update();
+    assert_eq!(
+        app.world.resource::<TimestampedInputs>().events.capacity(),
+        0
+    );
+
+    app.insert_resource(CaptureCapacityHint(1000));
+    app.update();
+    assert!(app.world.resource::<TimestampedInputs>().events.capacity() >= 1000);
+
+    // Replacing the recording reserves space again
+    app.insert_resource(TimestampedInputs::default());
+    app.update();
+    assert!(app.world.resource::<TimestampedInputs>().events.capacity() >= 1000);
+}
+
+#[test]
+fn capture_start_delay() {
+    let mut app = capture_app();
+    app.insert_resource(CaptureStartDelay(FrameCount(3)));
+
+    for _ in 0..4 {
+        app.world.send_event(TEST_PRESS);
+        app.update();
+    }
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    let frames: Vec<_> = timestamped_input
+        .events
+        .iter()
+        .map(|event| event.frame)
+        .collect();
+    assert_eq!(frames, [FrameCount(3), FrameCount(4)]);
+}
+
+#[test]
+fn capture_frame_limit() {
+    let mut app = capture_app();
+    app.insert_resource(CaptureFrameLimit(Some(FrameCount(2))));
+
+    for _ in 0..4 {
+        app.world.send_event(TEST_PRESS);
+        app.update();
+    }
+
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(
+        last_device_input_frame(timestamped_input),
+        Some(FrameCount(2))
+    );
+    assert_eq!(
+        *app.world.resource::<InputModesCaptured>(),
+        InputModesCaptured::DISABLE_ALL
+    );
+
+    let capture_stopped = app.world.resource::<Events<CaptureStopped>>();
+    let stopped: Vec<_> = capture_stopped
+        .get_reader()
+        .iter(capture_stopped)
+        .copied()
+        .collect();
+    assert_eq!(
+        stopped,
+        [CaptureStopped {
+            frame: FrameCount(3)
+        }]
+    );
+}
+
+#[test]
+fn capture_lifecycle_events() {
+    let mut app = capture_app();
+    app.insert_resource(InputModesCaptured::DISABLE_ALL);
+    app.update();
+
+    app.insert_resource(InputModesCaptured::ENABLE_ALL);
+    app.update();
+    let capture_started = app.world.resource::<Events<CaptureStarted>>();
+    let started: Vec<_> = capture_started
+        .iter_current_update_events()
+        .copied()
+        .collect();
+    assert_eq!(
+        started,
+        [CaptureStarted {
+            frame: FrameCount(2)
+        }]
+    );
+
+    // Changing which modes are enabled does not stop capture
+    app.insert_resource(InputModesCaptured {
+        keyboard: false,
+        ..InputModesCaptured::ENABLE_ALL
+    });
+    app.update();
+    app.insert_resource(InputModesCaptured::DISABLE_ALL);
+    app.update();
+
+    let capture_stopped = app.world.resource::<Events<CaptureStopped>>();
+    let stopped: Vec<_> = capture_stopped
+        .get_reader()
+        .iter(capture_stopped)
+        .copied()
+        .collect();
+    assert_eq!(
+        stopped,
+        [CaptureStopped {
+            frame: FrameCount(4)
+        }]
+    );
+    let capture_started = app.world.resource::<Events<CaptureStarted>>();
+    assert_eq!(
+        capture_started.get_reader().iter(capture_started).count(),
+        0
+    );
+}
+
+#[test]
+fn capture_passthrough() {
+    let mut app = capture_app();
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(TEST_PRESS);
+    app.update();
+
+    // By default, captured events remain readable by other systems
+    assert_eq!(app.world.resource::<Events<KeyboardInput>>().len(), 1);
+    assert_eq!(app.world.resource::<TimestampedInputs>().len(), 1);
+
+    app.insert_resource(CapturePassthrough(false));
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(TEST_RELEASE);
+    app.update();
+
+    assert_eq!(app.world.resource::<Events<KeyboardInput>>().len(), 0);
+    assert_eq!(app.world.resource::<TimestampedInputs>().len(), 2);
+}
+
+#[test]
+fn systems_ordered_before_capture_set() {
+    fn send_key(mut keyboard_events: EventWriter<KeyboardInput>) {
+        keyboard_events.send(TEST_PRESS);
+    }
+
+    let mut app = capture_app();
+    app.add_system(send_key.before(CaptureSet).in_base_set(CoreSet::Last));
+
+    // Events sent in `CoreSet::Last` are still recorded on the frame they are sent
+    app.update();
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+    assert_eq!(timestamped_input.events[0].frame, FrameCount(1));
+}
@@ -1,19 +1,45 @@
-// BLOCKED: add time strategy tests: https://github.com/bevyengine/bevy/issues/6146
-
+use bevy::input::gamepad::{
+    GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent,
+    GamepadInfo, Gamepads,
+};
 use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseWheel};
+use bevy::input::touch::{ForceTouch, TouchInput, TouchPhase};
 use bevy::input::ButtonState;
-use bevy::input::InputPlugin;
+use bevy::input::{InputPlugin, InputSystem};
 use bevy::prelude::*;
-use bevy::utils::Duration;
+use bevy::time::{fixed_timestep::FixedTime, TimeUpdateStrategy};
+use bevy::utils::{Duration, Instant};
 
-use bevy::window::WindowPlugin;
+use bevy::window::{PrimaryWindow, WindowFocused, WindowPlugin, WindowResized, WindowResolution};
 use leafwing_input_playback::frame_counting::FrameCount;
 
 use leafwing_input_playback::input_capture::InputCapturePlugin;
 use leafwing_input_playback::input_capture::InputModesCaptured;
+use leafwing_input_playback::input_playback::FixedPlaybackPlugin;
+use leafwing_input_playback::input_playback::GamepadRemap;
 use leafwing_input_playback::input_playback::InputPlaybackPlugin;
+use leafwing_input_playback::input_playback::LoopCompleted;
+use leafwing_input_playback::input_playback::PlaybackComplete;
+use leafwing_input_playback::input_playback::PlaybackControl;
+use leafwing_input_playback::input_playback::PlaybackJitter;
+use leafwing_input_playback::input_playback::PlaybackMaxEventsPerUpdate;
+use leafwing_input_playback::input_playback::PlaybackMixMode;
+use leafwing_input_playback::input_playback::PlaybackModes;
+use leafwing_input_playback::input_playback::PlaybackPauseWhenUnfocused;
+use leafwing_input_playback::input_playback::PlaybackRng;
+use leafwing_input_playback::input_playback::PlaybackScaleCursor;
+use leafwing_input_playback::input_playback::PlaybackSegment;
+use leafwing_input_playback::input_playback::PlaybackSet;
+use leafwing_input_playback::input_playback::PlaybackSpeed;
 use leafwing_input_playback::input_playback::PlaybackStrategy;
-use leafwing_input_playback::timestamped_input::TimestampedInputs;
+use leafwing_input_playback::input_playback::PlaybackWindowRemap;
+use leafwing_input_playback::input_playback::PlaybackWriteInputState;
+use leafwing_input_playback::input_playback::StepPlayback;
+use leafwing_input_playback::serde::{LoadError, PlaybackFilePath};
+use leafwing_input_playback::timestamped_input::{
+    InputEvent, RecordingMetadata, TimestampedInputs,
+};
 
 const TEST_PRESS: KeyboardInput = KeyboardInput {
     scan_code: 1,
@@ -40,6 +66,19 @@ fn playback_app(strategy: PlaybackStrategy) -> App {
     app
 }
 
+/// Runs a single update, advancing [`Time`] by exactly `delta` rather than reading the system clock.
+///
+/// Because Bevy reports a delta of zero on the very first update, start each test with a plain `app.update()`.
+fn update_with_delta(app: &mut App, delta: Duration) {
+    let last_update = app
+        .world
+        .resource::<Time>()
+        .last_update()
+        .unwrap_or_else(Instant::now);
+    app.insert_resource(TimeUpdateStrategy::ManualInstant(last_update + delta));
+    app.update();
+}
+
 fn simple_timestamped_input() -> TimestampedInputs {
     let mut inputs = TimestampedInputs::default();
     inputs.send(FrameCount(1), Duration::from_secs(0), TEST_PRESS.into());
@@ -178,10 +217,42 @@ fn playback_strategy_frame() {
     assert_eq!(timestamped_input.cursor, 5);
 }
 
+#[test]
+fn playback_strategy_frame_enabled_mid_run() {
+    let mut app = playback_app(PlaybackStrategy::Paused);
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // Frames 1 and 2 elapse without playback
+    app.update();
+    app.update();
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.cursor, 0);
+
+    // Playback begins on frame 3:
+    // all unread events up to and including those recorded on frame 3 are played
+    app.insert_resource(PlaybackStrategy::FrameCount);
+    app.update();
+    assert_eq!(*app.world.resource::<FrameCount>(), FrameCount(3));
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.cursor, 5);
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.len(), 5);
+
+    // The events from frame 3 are not played a second time on frame 4
+    app.update();
+    let timestamped_input = app.world.resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.cursor, 5);
+    let mut input_events = app.world.resource_mut::<Events<KeyboardInput>>();
+    // Only the double-buffered events from the previous frame remain
+    assert_eq!(input_events.len(), 5);
+    input_events.update();
+    assert_eq!(input_events.len(), 0);
+}
+
 #[test]
 fn playback_strategy_frame_range_once() {
     let strategy = PlaybackStrategy::FrameRangeOnce(FrameCount(2), FrameCount(5));
-    let mut app = playback_app(strategy);
+    let mut app = playback_app(strategy.clone());
     *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
 
     let timestamped_input = app.world.resource::<TimestampedInputs>();
@@ -216,10 +287,234 @@ fn playback_strategy_frame_range_once() {
     );
 }
 
+#[test]
+fn playback_strategy_playlist() {
+    let strategy = PlaybackStrategy::Playlist(vec![
+        (FrameCount(2), FrameCount(3)),
+        (FrameCount(1), FrameCount(1)),
+        (FrameCount(0), FrameCount(1)),
+    ]);
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // Frame 2, from the first range
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 2);
+
+    // A single update is left empty between ranges
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 0);
+
+    // Frame 0, from the final range, as the empty range is skipped
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 1);
+    assert_eq!(*app.world.resource::<PlaybackStrategy>(), strategy);
+
+    // Paused
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 0);
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+    let complete_events = app.world.resource::<Events<PlaybackComplete>>();
+    let completions: Vec<_> = complete_events
+        .iter_current_update_events()
+        .cloned()
+        .collect();
+    assert_eq!(completions, [PlaybackComplete { strategy }]);
+}
+
+#[test]
+fn playback_strategy_composite() {
+    let strategy = PlaybackStrategy::Composite(vec![
+        PlaybackSegment::once(FrameCount(0), FrameCount(1)),
+        PlaybackSegment {
+            start: FrameCount(1),
+            end: FrameCount(2),
+            loops: Some(0),
+        },
+        PlaybackSegment::forever(FrameCount(2), FrameCount(3)),
+    ]);
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // The intro plays once, then the segment that plays zero times is skipped,
+    // and the final segment loops with a single empty update between each pass
+    let mut played = Vec::new();
+    let mut loops = Vec::new();
+    for _ in 0..7 {
+        app.update();
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        played.push(input_events.iter_current_update_events().count());
+        let loop_events = app.world.resource::<Events<LoopCompleted>>();
+        loops.extend(
+            loop_events
+                .iter_current_update_events()
+                .map(|event| event.iteration),
+        );
+    }
+    assert_eq!(played, [1, 0, 2, 0, 2, 0, 2]);
+    assert_eq!(loops, [1, 2]);
+    assert_eq!(*app.world.resource::<PlaybackStrategy>(), strategy);
+    let complete_events = app.world.resource::<Events<PlaybackComplete>>();
+    assert_eq!(complete_events.len(), 0);
+
+    // A composite of finite segments pauses once they have all been played
+    let strategy = PlaybackStrategy::Composite(vec![PlaybackSegment {
+        start: FrameCount(0),
+        end: FrameCount(1),
+        loops: Some(2),
+    }]);
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+    let mut played = Vec::new();
+    for _ in 0..5 {
+        app.update();
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        played.push(input_events.iter_current_update_events().count());
+    }
+    assert_eq!(played, [1, 0, 1, 0, 0]);
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn pause_and_resume_playlist() {
+    let strategy = PlaybackStrategy::Playlist(vec![
+        (FrameCount(2), FrameCount(3)),
+        (FrameCount(0), FrameCount(1)),
+    ]);
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // Frame 2, from the first range, then the empty update between ranges
+    app.update();
+    app.update();
+
+    app.world
+        .resource_scope(|world, mut control: Mut<PlaybackControl>| {
+            control.pause(&mut world.resource_mut::<PlaybackStrategy>());
+        });
+    app.update();
+    app.world
+        .resource_scope(|world, mut control: Mut<PlaybackControl>| {
+            control.resume(&mut world.resource_mut::<PlaybackStrategy>());
+        });
+
+    // Frame 0, from the second range, rather than starting again from the first
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 1);
+
+    app.update();
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn pause_and_resume_composite() {
+    let strategy = PlaybackStrategy::Composite(vec![
+        PlaybackSegment::once(FrameCount(0), FrameCount(1)),
+        PlaybackSegment::forever(FrameCount(2), FrameCount(3)),
+    ]);
+    let mut app = playback_app(strategy);
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    let mut played = Vec::new();
+    let mut loops = Vec::new();
+    for update in 0..7 {
+        // Pausing and resuming neither restarts the composite nor its loop count
+        if update == 4 {
+            app.world
+                .resource_scope(|world, mut control: Mut<PlaybackControl>| {
+                    control.pause(&mut world.resource_mut::<PlaybackStrategy>());
+                });
+            app.update();
+            app.world
+                .resource_scope(|world, mut control: Mut<PlaybackControl>| {
+                    control.resume(&mut world.resource_mut::<PlaybackStrategy>());
+                });
+        }
+
+        app.update();
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        played.push(input_events.iter_current_update_events().count());
+        let loop_events = app.world.resource::<Events<LoopCompleted>>();
+        loops.extend(
+            loop_events
+                .iter_current_update_events()
+                .map(|event| event.iteration),
+        );
+    }
+    assert_eq!(played, [1, 0, 2, 0, 2, 0, 2]);
+    assert_eq!(loops, [1, 2]);
+}
+
+#[test]
+fn pause_when_unfocused() {
+    let mut app = playback_app(PlaybackStrategy::FrameRangeOnce(
+        FrameCount(1),
+        FrameCount(3),
+    ));
+    app.insert_resource(PlaybackPauseWhenUnfocused(true));
+    *app.world.resource_mut::<TimestampedInputs>() = simple_timestamped_input();
+
+    let mut primary_window = app
+        .world
+        .query_filtered::<&mut Window, With<PrimaryWindow>>();
+    primary_window.single_mut(&mut app.world).focused = false;
+    app.update();
+    app.update();
+
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.len(), 0);
+    assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 0);
+
+    // Playback resumes from the start of the range once focused
+    primary_window.single_mut(&mut app.world).focused = true;
+    app.update();
+    let input = app.world.resource::<Input<KeyCode>>();
+    assert!(input.pressed(KeyCode::F));
+}
+
+#[test]
+fn fixed_timestep_playback() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(InputPlugin)
+        .add_plugin(FixedPlaybackPlugin)
+        .insert_resource(FixedTime::new(Duration::from_millis(10)));
+    *app.world.resource_mut::<PlaybackStrategy>() = PlaybackStrategy::FrameCount;
+    *app.world.resource_mut::<TimestampedInputs>() = simple_timestamped_input();
+
+    app.update();
+    assert_eq!(*app.world.resource::<FrameCount>(), FrameCount(0));
+
+    // A single slow frame runs two fixed ticks, playing back the events of both
+    update_with_delta(&mut app, Duration::from_millis(25));
+    assert_eq!(*app.world.resource::<FrameCount>(), FrameCount(2));
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.len(), 2);
+
+    // Frames too short for a fixed tick play nothing
+    update_with_delta(&mut app, Duration::from_millis(1));
+    assert_eq!(*app.world.resource::<FrameCount>(), FrameCount(2));
+}
+
 #[test]
 fn playback_strategy_frame_range_loop() {
     let strategy = PlaybackStrategy::FrameRangeLoop(FrameCount(2), FrameCount(5));
-    let mut app = playback_app(strategy);
+    let mut app = playback_app(strategy.clone());
     *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
 
     let timestamped_input = app.world.resource::<TimestampedInputs>();
@@ -256,3 +551,1333 @@ fn playback_strategy_frame_range_loop() {
         PlaybackStrategy::FrameRangeLoop(FrameCount(2), FrameCount(5))
     );
 }
+
+#[test]
+fn touch_force_round_trip() {
+    let forces = [
+        Some(ForceTouch::Normalized(0.25)),
+        Some(ForceTouch::Calibrated {
+            force: 1.5,
+            max_possible_force: 4.0,
+            altitude_angle: Some(0.7),
+        }),
+        None,
+    ];
+
+    // Capture a touch whose force varies over time
+    let mut app = playback_app(PlaybackStrategy::Paused);
+    app.add_plugin(InputCapturePlugin);
+    for (i, force) in forces.iter().enumerate() {
+        let phase = if i == 0 {
+            TouchPhase::Started
+        } else {
+            TouchPhase::Moved
+        };
+        app.world
+            .resource_mut::<Events<TouchInput>>()
+            .send(TouchInput {
+                phase,
+                position: Vec2::new(10.0 * i as f32, 20.0),
+                force: *force,
+                id: 0,
+            });
+        app.update();
+    }
+
+    // The forces survive serialization
+    let captured = app.world.resource::<TimestampedInputs>().clone();
+    let serialized = ron::to_string(&captured).unwrap();
+    let deserialized: TimestampedInputs = ron::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, captured);
+
+    // And are replayed faithfully
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = deserialized;
+    let mut replayed_forces = Vec::new();
+    for _ in 0..forces.len() {
+        app.update();
+        let touch_events = app.world.resource::<Events<TouchInput>>();
+        replayed_forces.extend(touch_events.iter_current_update_events().map(|e| e.force));
+    }
+    assert_eq!(replayed_forces, forces);
+}
+
+#[test]
+fn mix_mode_additive() {
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    *app.world.resource_mut::<TimestampedInputs>() = inputs;
+
+    // The "live" input uses another key
+    let live_press = KeyboardInput {
+        scan_code: 2,
+        key_code: Some(KeyCode::G),
+        state: ButtonState::Pressed,
+    };
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(live_press);
+    app.update();
+
+    // Both the live and played back inputs are received
+    let input = app.world.resource::<Input<KeyCode>>();
+    assert!(input.pressed(KeyCode::F));
+    assert!(input.pressed(KeyCode::G));
+}
+
+#[test]
+fn mix_mode_override() {
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    app.insert_resource(PlaybackMixMode::Override);
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    *app.world.resource_mut::<TimestampedInputs>() = inputs;
+
+    let live_press = KeyboardInput {
+        scan_code: 2,
+        key_code: Some(KeyCode::G),
+        state: ButtonState::Pressed,
+    };
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(live_press);
+    app.update();
+
+    // Only the played back input is received
+    let input = app.world.resource::<Input<KeyCode>>();
+    assert!(input.pressed(KeyCode::F));
+    assert!(!input.pressed(KeyCode::G));
+
+    // Live input is received again once playback is paused
+    app.insert_resource(PlaybackStrategy::Paused);
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(live_press);
+    app.update();
+
+    let input = app.world.resource::<Input<KeyCode>>();
+    assert!(input.pressed(KeyCode::G));
+}
+
+#[test]
+fn mix_mode_override_misses_late_gamepad_input() {
+    // Gamepad input from `bevy_gilrs` is sent during `CoreSet::PreUpdate`, after playback has run
+    fn send_live_gamepad_input(mut gamepad_events: EventWriter<GamepadEvent>) {
+        gamepad_events.send(GamepadEvent::Button(GamepadButtonChangedEvent::new(
+            Gamepad::new(0),
+            GamepadButtonType::South,
+            1.0,
+        )));
+    }
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    app.insert_resource(PlaybackMixMode::Override).add_system(
+        send_live_gamepad_input
+            .before(InputSystem)
+            .in_base_set(CoreSet::PreUpdate),
+    );
+    app.update();
+
+    // These events are sent too late to be discarded
+    let gamepad_events = app.world.resource::<Events<GamepadEvent>>();
+    assert_eq!(gamepad_events.iter_current_update_events().count(), 1);
+}
+
+#[test]
+fn shift_click_reproduction() {
+    let shift_press = KeyboardInput {
+        scan_code: 42,
+        key_code: Some(KeyCode::LShift),
+        state: ButtonState::Pressed,
+    };
+    let click = MouseButtonInput {
+        button: MouseButton::Left,
+        state: ButtonState::Pressed,
+    };
+
+    // Capture a Shift+Click, with the click arriving first within the frame
+    let mut app = playback_app(PlaybackStrategy::Paused);
+    app.add_plugin(InputCapturePlugin);
+    app.world
+        .resource_mut::<Events<MouseButtonInput>>()
+        .send(click);
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(shift_press);
+    app.update();
+
+    // The modifier is recorded before the click
+    let captured = app.world.resource::<TimestampedInputs>().clone();
+    assert_eq!(captured.len(), 2);
+    assert_eq!(captured.events[0].frame, captured.events[1].frame);
+    assert!(matches!(
+        captured.events[0].input_event,
+        InputEvent::Keyboard(_)
+    ));
+    assert!(matches!(
+        captured.events[1].input_event,
+        InputEvent::MouseButton(_)
+    ));
+
+    // On playback, the click is never seen without the modifier
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = captured;
+    app.update();
+
+    let mouse_input = app.world.resource::<Input<MouseButton>>();
+    assert!(mouse_input.just_pressed(MouseButton::Left));
+    let keyboard_input = app.world.resource::<Input<KeyCode>>();
+    assert!(keyboard_input.pressed(KeyCode::LShift));
+}
+
+#[test]
+fn playback_strategy_fit_duration() {
+    // The recording spans 3 seconds; stretch it to 6
+    let strategy = PlaybackStrategy::FitDuration(Duration::from_secs(6));
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // Events at 0 seconds
+    app.update();
+    assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 1);
+
+    // Each real second plays half a second of the recording
+    let expected_cursors = [1, 2, 2, 3, 3];
+    for expected_cursor in expected_cursors {
+        update_with_delta(&mut app, Duration::from_secs(1));
+        assert_eq!(
+            app.world.resource::<TimestampedInputs>().cursor,
+            expected_cursor
+        );
+        assert_eq!(*app.world.resource::<PlaybackStrategy>(), strategy);
+    }
+
+    // After 6 seconds, the final events at 3 seconds are played and playback stops
+    update_with_delta(&mut app, Duration::from_secs(1));
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 2);
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn deterministic_intra_frame_order() {
+    use leafwing_input_playback::input_playback::sort_for_playback;
+    use leafwing_input_playback::timestamped_input::InputKind;
+
+    let click = MouseButtonInput {
+        button: MouseButton::Left,
+        state: ButtonState::Pressed,
+    };
+
+    // The same events, captured with two different interleavings between kinds
+    let mut first_run = TimestampedInputs::default();
+    first_run.send(FrameCount(1), Duration::ZERO, click.into());
+    first_run.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    first_run.send(FrameCount(1), Duration::ZERO, TEST_RELEASE.into());
+    first_run.send(FrameCount(2), Duration::ZERO, click.into());
+    first_run.send(FrameCount(2), Duration::ZERO, TEST_PRESS.into());
+
+    let mut second_run = TimestampedInputs::default();
+    second_run.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    second_run.send(FrameCount(1), Duration::ZERO, click.into());
+    second_run.send(FrameCount(1), Duration::ZERO, TEST_RELEASE.into());
+    second_run.send(FrameCount(2), Duration::ZERO, TEST_PRESS.into());
+    second_run.send(FrameCount(2), Duration::ZERO, click.into());
+
+    sort_for_playback(&mut first_run.events);
+    sort_for_playback(&mut second_run.events);
+    assert_eq!(first_run, second_run);
+
+    // Keyboard events come first within each frame, keeping their recorded order
+    let kinds: Vec<_> = first_run
+        .events
+        .iter()
+        .map(|event| (event.frame, event.input_event.kind()))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (FrameCount(1), InputKind::Keyboard),
+            (FrameCount(1), InputKind::Keyboard),
+            (FrameCount(1), InputKind::MouseButton),
+            (FrameCount(2), InputKind::Keyboard),
+            (FrameCount(2), InputKind::MouseButton),
+        ]
+    );
+    assert_eq!(
+        first_run.events[0].input_event,
+        InputEvent::Keyboard(TEST_PRESS)
+    );
+}
+
+#[test]
+fn load_recording_at_startup() {
+    let path = std::env::temp_dir().join("leafwing_input_playback_load_recording.ron");
+    let mut recording = complex_timestamped_input();
+    recording.cursor = 2;
+    recording.write_ron(&path).unwrap();
+
+    let mut app = playback_app(PlaybackStrategy::Paused);
+    app.insert_resource(PlaybackFilePath::new(path.to_str().unwrap()));
+    app.update();
+    std::fs::remove_file(&path).unwrap();
+
+    // Loaded recordings always start from the beginning
+    recording.cursor = 0;
+    assert_eq!(*app.world.resource::<TimestampedInputs>(), recording);
+}
+
+#[test]
+fn load_errors() {
+    let path = std::env::temp_dir().join("leafwing_input_playback_load_errors.ron");
+
+    let missing = TimestampedInputs::read_ron(&path);
+    assert!(matches!(missing, Err(LoadError::Io(_))));
+
+    std::fs::write(&path, "not a recording").unwrap();
+    let malformed = TimestampedInputs::read_ron(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(malformed, Err(LoadError::Deserialize(_))));
+}
+
+#[test]
+fn read_metadata() {
+    let path = std::env::temp_dir().join("leafwing_input_playback_read_metadata.ron");
+    let mut inputs = complex_timestamped_input();
+    inputs.metadata = RecordingMetadata::new("tutorial");
+    inputs
+        .metadata
+        .extra
+        .insert("build".to_string(), "nightly".to_string());
+
+    inputs.write_ron(&path).unwrap();
+    let metadata = TimestampedInputs::read_metadata(&path).unwrap();
+    let loaded = TimestampedInputs::read_ron(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(metadata, inputs.metadata);
+    assert_eq!(loaded, inputs);
+}
+
+#[test]
+fn playback_strategy_time_range_once() {
+    let strategy = PlaybackStrategy::TimeRangeOnce(Duration::from_secs(1), Duration::from_secs(3));
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // No time has passed yet
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 0);
+
+    // Replays the events in the time range [1s, 3s), one second at a time
+    for _ in 0..2 {
+        update_with_delta(&mut app, Duration::from_secs(1));
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        assert_eq!(input_events.iter_current_update_events().count(), 1);
+        assert_eq!(*app.world.resource::<PlaybackStrategy>(), strategy);
+    }
+
+    // The events at exactly 3s are excluded, and playback is paused
+    update_with_delta(&mut app, Duration::from_secs(1));
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 0);
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn playback_strategy_time_range_once_overshoot() {
+    let strategy = PlaybackStrategy::TimeRangeOnce(Duration::ZERO, Duration::from_secs(3));
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+    app.update();
+
+    // [0s, 2s)
+    update_with_delta(&mut app, Duration::from_secs(2));
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 2);
+
+    // This update overshoots the end of the range, but the events at 3s are still excluded
+    update_with_delta(&mut app, Duration::from_secs(2));
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 1);
+
+    update_with_delta(&mut app, Duration::from_secs(2));
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn playback_strategy_time_range_loop() {
+    let strategy = PlaybackStrategy::TimeRangeLoop(Duration::from_secs(1), Duration::from_secs(3));
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+    app.update();
+
+    for _ in 0..2 {
+        // Replays the events in the time range [1s, 3s), one second at a time
+        for _ in 0..2 {
+            update_with_delta(&mut app, Duration::from_secs(1));
+            let input_events = app.world.resource::<Events<KeyboardInput>>();
+            assert_eq!(input_events.iter_current_update_events().count(), 1);
+        }
+
+        // Spacing update
+        update_with_delta(&mut app, Duration::from_secs(1));
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        assert_eq!(input_events.iter_current_update_events().count(), 0);
+        assert_eq!(*app.world.resource::<PlaybackStrategy>(), strategy);
+    }
+}
+
+/// Counts the one-second updates required to play back every event in [`complex_timestamped_input`] at the given `speed`.
+fn updates_to_finish(speed: f32) -> usize {
+    let strategy = PlaybackStrategy::TimeRangeOnce(Duration::ZERO, Duration::from_secs(10));
+    let mut app = playback_app(strategy.clone());
+    app.insert_resource(PlaybackSpeed(speed));
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+    app.update();
+
+    let mut updates = 0;
+    while app.world.resource::<TimestampedInputs>().cursor < 5 {
+        update_with_delta(&mut app, Duration::from_secs(1));
+        updates += 1;
+    }
+    updates
+}
+
+#[test]
+fn playback_speed() {
+    // The final events are recorded at 3s, and so are played on the update covering [3s, 4s)
+    assert_eq!(updates_to_finish(1.0), 4);
+    assert_eq!(updates_to_finish(2.0), 2);
+    // ...or [3s, 3.5s) at half speed
+    assert_eq!(updates_to_finish(0.5), 7);
+}
+
+#[test]
+fn non_positive_playback_speed_pauses() {
+    for speed in [0.0, -1.0, f32::NAN] {
+        let strategy = PlaybackStrategy::TimeRangeOnce(Duration::ZERO, Duration::from_secs(10));
+        let mut app = playback_app(strategy.clone());
+        app.insert_resource(PlaybackSpeed(speed));
+        *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+        app.update();
+
+        for _ in 0..3 {
+            update_with_delta(&mut app, Duration::from_secs(1));
+        }
+        assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 0);
+        assert_eq!(*app.world.resource::<PlaybackStrategy>(), strategy);
+    }
+}
+
+#[test]
+fn playback_complete_sent_once() {
+    let strategy = PlaybackStrategy::FrameRangeOnce(FrameCount(2), FrameCount(5));
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    let mut completions = Vec::new();
+    for _ in 0..10 {
+        app.update();
+        let complete_events = app.world.resource::<Events<PlaybackComplete>>();
+        completions.extend(complete_events.iter_current_update_events().cloned());
+    }
+
+    assert_eq!(completions, [PlaybackComplete { strategy }]);
+}
+
+#[test]
+fn playback_strategy_frame_range_loop_n() {
+    let mut app = playback_app(PlaybackStrategy::FrameRangeLoopN(
+        FrameCount(2),
+        FrameCount(4),
+        3,
+    ));
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    let mut keys_played = 0;
+    let mut iterations = Vec::new();
+    let mut completions = Vec::new();
+    // Each loop takes 2 frames of playback, plus a spacing frame
+    for _ in 0..15 {
+        app.update();
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        keys_played += input_events.iter_current_update_events().count();
+        let loop_events = app.world.resource::<Events<LoopCompleted>>();
+        iterations.extend(
+            loop_events
+                .iter_current_update_events()
+                .map(|event| event.iteration),
+        );
+        let complete_events = app.world.resource::<Events<PlaybackComplete>>();
+        completions.extend(complete_events.iter_current_update_events().cloned());
+    }
+
+    // Frames 2 and 3 hold three events, played once per loop
+    assert_eq!(keys_played, 9);
+    assert_eq!(iterations, [1, 2, 3]);
+    assert_eq!(
+        completions,
+        [PlaybackComplete {
+            strategy: PlaybackStrategy::FrameRangeLoopN(FrameCount(2), FrameCount(4), 1)
+        }]
+    );
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn loop_completed_counts_iterations() {
+    let strategy = PlaybackStrategy::FrameRangeLoop(FrameCount(2), FrameCount(5));
+    let mut app = playback_app(strategy.clone());
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    let mut iterations = Vec::new();
+    let mut run_updates = |app: &mut App, n: usize| {
+        for _ in 0..n {
+            app.update();
+            let loop_events = app.world.resource::<Events<LoopCompleted>>();
+            iterations.extend(
+                loop_events
+                    .iter_current_update_events()
+                    .map(|event| event.iteration),
+            );
+        }
+    };
+
+    // Each loop takes 3 frames of playback, plus a spacing frame
+    run_updates(&mut app, 12);
+
+    // Replacing the strategy restarts the count
+    app.insert_resource(strategy);
+    run_updates(&mut app, 4);
+
+    assert_eq!(iterations, [1, 2, 3, 1]);
+}
+
+#[test]
+fn mouse_motion_playback() {
+    let motion = MouseMotion {
+        delta: Vec2::new(-4.0, 0.25),
+    };
+    let mut recording = TimestampedInputs::default();
+    recording.send(FrameCount(1), Duration::ZERO, motion.into());
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+    app.update();
+
+    let motion_events = app.world.resource::<Events<MouseMotion>>();
+    let played: Vec<_> = motion_events
+        .iter_current_update_events()
+        .copied()
+        .collect();
+    assert_eq!(played, [motion]);
+}
+
+#[test]
+fn two_finger_pinch_playback() {
+    let finger = |id: u64, x: f32| TouchInput {
+        phase: TouchPhase::Moved,
+        position: Vec2::new(x, 300.0),
+        force: None,
+        id,
+    };
+
+    // Capture both fingers of a pinch on the same frame
+    let mut app = playback_app(PlaybackStrategy::Paused);
+    app.add_plugin(InputCapturePlugin);
+    app.update();
+    let mut touch_events = app.world.resource_mut::<Events<TouchInput>>();
+    touch_events.send(finger(0, 100.0));
+    touch_events.send(finger(1, 500.0));
+    app.update();
+
+    let captured = app.world.resource::<TimestampedInputs>().clone();
+    assert_eq!(captured.len(), 2);
+    assert_eq!(captured.events[0].frame, FrameCount(2));
+    assert_eq!(captured.events[1].frame, FrameCount(2));
+
+    // Both touch points are re-emitted together, with their finger ids intact
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = captured;
+    app.update();
+    let touch_events = app.world.resource::<Events<TouchInput>>();
+    assert_eq!(touch_events.iter_current_update_events().count(), 0);
+
+    app.update();
+    let touch_events = app.world.resource::<Events<TouchInput>>();
+    let replayed: Vec<_> = touch_events.iter_current_update_events().copied().collect();
+    assert_eq!(replayed, [finger(0, 100.0), finger(1, 500.0)]);
+}
+
+#[test]
+fn gamepad_remap() {
+    let recorded = Gamepad::new(3);
+    let connected = Gamepad::new(0);
+    let mut recording = TimestampedInputs::default();
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        GamepadEvent::Button(GamepadButtonChangedEvent::new(
+            recorded,
+            GamepadButtonType::South,
+            1.0,
+        ))
+        .into(),
+    );
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    app.insert_resource(GamepadRemap([(recorded, connected)].into_iter().collect()));
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+    app.update();
+
+    let gamepad_events = app.world.resource::<Events<GamepadEvent>>();
+    let played: Vec<_> = gamepad_events
+        .iter_current_update_events()
+        .cloned()
+        .collect();
+    assert_eq!(
+        played,
+        [GamepadEvent::Button(GamepadButtonChangedEvent::new(
+            connected,
+            GamepadButtonType::South,
+            1.0,
+        ))]
+    );
+}
+
+#[test]
+fn gamepad_connection_playback() {
+    let recorded = Gamepad::new(3);
+    let connected = Gamepad::new(0);
+    let mut recording = TimestampedInputs::default();
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        GamepadEvent::Connection(GamepadConnectionEvent::new(
+            recorded,
+            GamepadConnection::Connected(GamepadInfo {
+                name: "Test gamepad".to_string(),
+            }),
+        ))
+        .into(),
+    );
+    recording.send(
+        FrameCount(3),
+        Duration::ZERO,
+        GamepadEvent::Connection(GamepadConnectionEvent::new(
+            recorded,
+            GamepadConnection::Disconnected,
+        ))
+        .into(),
+    );
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    app.insert_resource(GamepadRemap([(recorded, connected)].into_iter().collect()));
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+
+    // Connections are played back on their recorded frames, to the remapped gamepad
+    app.update();
+    assert!(app.world.resource::<Gamepads>().contains(connected));
+    assert!(!app.world.resource::<Gamepads>().contains(recorded));
+
+    app.update();
+    assert!(app.world.resource::<Gamepads>().contains(connected));
+
+    app.update();
+    assert!(!app.world.resource::<Gamepads>().contains(connected));
+}
+
+#[test]
+fn write_input_state_without_input_plugin() {
+    // Without the `InputPlugin`, nothing converts the played back events into `Input` state
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin::default())
+        .add_event::<KeyboardInput>()
+        .add_event::<MouseButtonInput>()
+        .add_event::<MouseWheel>()
+        .add_event::<MouseMotion>()
+        .add_event::<TouchInput>()
+        .add_event::<GamepadEvent>()
+        .init_resource::<Input<KeyCode>>()
+        .init_resource::<Input<MouseButton>>()
+        .add_plugin(InputPlaybackPlugin)
+        .insert_resource(PlaybackStrategy::FrameCount)
+        .insert_resource(PlaybackWriteInputState(true));
+    *app.world.resource_mut::<TimestampedInputs>() = simple_timestamped_input();
+
+    app.update();
+    assert!(app
+        .world
+        .resource::<Input<KeyCode>>()
+        .pressed(TEST_PRESS.key_code.unwrap()));
+    // Events are still sent
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 1);
+
+    app.update();
+    assert!(!app
+        .world
+        .resource::<Input<KeyCode>>()
+        .pressed(TEST_PRESS.key_code.unwrap()));
+}
+
+#[test]
+fn max_events_per_update() {
+    let mut recording = TimestampedInputs::default();
+    for _ in 0..5 {
+        recording.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    }
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+        }
+        .into(),
+    );
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    app.insert_resource(PlaybackMaxEventsPerUpdate(2));
+    // Mouse buttons are skipped, and so do not count towards the limit
+    app.insert_resource(PlaybackModes {
+        mouse_buttons: false,
+        ..PlaybackModes::ENABLE_ALL
+    });
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+
+    // Excess events are deferred to the following updates
+    for expected in [2, 2, 1, 0] {
+        app.update();
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        assert_eq!(input_events.iter_current_update_events().count(), expected);
+    }
+    assert_eq!(app.world.resource::<TimestampedInputs>().remaining(), 0);
+}
+
+#[test]
+fn max_events_per_update_frame_range() {
+    let mut recording = TimestampedInputs::default();
+    for _ in 0..5 {
+        recording.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    }
+    // The end of the range is excluded
+    recording.send(FrameCount(2), Duration::ZERO, TEST_RELEASE.into());
+
+    let strategy = PlaybackStrategy::FrameRangeOnce(FrameCount(1), FrameCount(2));
+    let mut app = playback_app(strategy.clone());
+    app.insert_resource(PlaybackMaxEventsPerUpdate(2));
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+
+    // Deferred events are still played once the range has been covered, before it finishes
+    for expected in [2, 2, 1] {
+        app.update();
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        let pressed: Vec<_> = input_events
+            .iter_current_update_events()
+            .map(|event| event.state)
+            .collect();
+        assert_eq!(pressed, vec![ButtonState::Pressed; expected]);
+    }
+
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+    let complete_events = app.world.resource::<Events<PlaybackComplete>>();
+    let completions: Vec<_> = complete_events
+        .iter_current_update_events()
+        .cloned()
+        .collect();
+    assert_eq!(completions, [PlaybackComplete { strategy }]);
+}
+
+#[test]
+fn playback_modes_skip_disabled_input() {
+    let gamepad = Gamepad::new(0);
+    let mut recording = TimestampedInputs::default();
+    recording.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        GamepadEvent::Button(GamepadButtonChangedEvent::new(
+            gamepad,
+            GamepadButtonType::South,
+            1.0,
+        ))
+        .into(),
+    );
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    app.insert_resource(PlaybackModes {
+        gamepad: false,
+        ..PlaybackModes::ENABLE_ALL
+    });
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+    app.update();
+
+    let gamepad_events = app.world.resource::<Events<GamepadEvent>>();
+    assert_eq!(gamepad_events.len(), 0);
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.len(), 1);
+    let input = app.world.resource::<Input<KeyCode>>();
+    assert!(input.pressed(KeyCode::F));
+}
+
+#[test]
+fn remap_keys() {
+    let mut recording = simple_timestamped_input();
+    let key_codes = [(KeyCode::F, KeyCode::G)].into_iter().collect();
+    recording.remap_keys(&key_codes, None);
+
+    // Scan codes are untouched unless requested
+    assert!(matches!(
+        recording.events[0].input_event,
+        InputEvent::Keyboard(KeyboardInput {
+            key_code: Some(KeyCode::G),
+            scan_code: 1,
+            ..
+        })
+    ));
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = recording.clone();
+    app.update();
+
+    let input = app.world.resource::<Input<KeyCode>>();
+    assert!(input.pressed(KeyCode::G));
+    assert!(!input.pressed(KeyCode::F));
+
+    let scan_codes = [(1, 34)].into_iter().collect();
+    recording.remap_keys(&Default::default(), Some(&scan_codes));
+    assert!(matches!(
+        recording.events[1].input_event,
+        InputEvent::Keyboard(KeyboardInput {
+            key_code: Some(KeyCode::G),
+            scan_code: 34,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn playback_strategy_real_time() {
+    let mut app = playback_app(PlaybackStrategy::RealTime);
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // Events recorded at 0s are released immediately
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 1);
+
+    update_with_delta(&mut app, Duration::from_secs(1));
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 1);
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::RealTime
+    );
+
+    // After a long frame, all overdue events are released at once
+    update_with_delta(&mut app, Duration::from_secs(5));
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 3);
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn playback_strategy_step() {
+    let mut app = playback_app(PlaybackStrategy::Step);
+    *app.world.resource_mut::<TimestampedInputs>() = simple_timestamped_input();
+
+    // Nothing is played without stepping
+    app.update();
+    app.update();
+    assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 0);
+
+    app.world.send_event(StepPlayback);
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 1);
+    assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 1);
+
+    // Stepping past the end does nothing
+    for _ in 0..3 {
+        app.world.send_event(StepPlayback);
+        app.update();
+    }
+    assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 2);
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Step
+    );
+}
+
+#[test]
+fn pause_and_resume() {
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = complex_timestamped_input();
+
+    // Frames 0 and 1
+    app.update();
+    assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 2);
+
+    app.world
+        .resource_scope(|world, mut control: Mut<PlaybackControl>| {
+            control.pause(&mut world.resource_mut::<PlaybackStrategy>());
+        });
+    for _ in 0..3 {
+        app.update();
+        let input_events = app.world.resource::<Events<KeyboardInput>>();
+        assert_eq!(input_events.iter_current_update_events().count(), 0);
+    }
+    assert_eq!(app.world.resource::<TimestampedInputs>().cursor, 2);
+
+    app.world
+        .resource_scope(|world, mut control: Mut<PlaybackControl>| {
+            control.resume(&mut world.resource_mut::<PlaybackStrategy>());
+        });
+    assert_eq!(
+        *app.world.resource::<PlaybackStrategy>(),
+        PlaybackStrategy::FrameCount
+    );
+
+    // Playback continues from the same cursor
+    app.update();
+    let input_events = app.world.resource::<Events<KeyboardInput>>();
+    assert_eq!(input_events.iter_current_update_events().count(), 3);
+}
+
+#[test]
+fn window_remap() {
+    let recorded_window = Entity::from_raw(999);
+    let cursor_moved = CursorMoved {
+        window: recorded_window,
+        position: Vec2::new(20.0, 40.0),
+    };
+    let mut recording = TimestampedInputs::default();
+    recording.send(FrameCount(1), Duration::ZERO, cursor_moved.clone().into());
+
+    // The recorded window does not exist, so the event is dropped
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = recording.clone();
+    app.update();
+    let cursor_events = app.world.resource::<Events<CursorMoved>>();
+    assert_eq!(cursor_events.iter_current_update_events().count(), 0);
+
+    // Once remapped, it is sent to the live window
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    let live_window = app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world);
+    app.insert_resource(PlaybackWindowRemap(
+        [(recorded_window, live_window)].into_iter().collect(),
+    ));
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+    app.update();
+
+    let cursor_events = app.world.resource::<Events<CursorMoved>>();
+    let played: Vec<_> = cursor_events
+        .iter_current_update_events()
+        .map(|event| event.window)
+        .collect();
+    assert_eq!(played, [live_window]);
+    let window = app.world.get::<Window>(live_window).unwrap();
+    assert_eq!(window.cursor_position(), Some(cursor_moved.position));
+}
+
+#[test]
+fn window_events_playback() {
+    let recorded_window = Entity::from_raw(999);
+    let mut recording = TimestampedInputs::default();
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        WindowResized {
+            window: recorded_window,
+            width: 800.0,
+            height: 600.0,
+        }
+        .into(),
+    );
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        WindowFocused {
+            window: recorded_window,
+            focused: false,
+        }
+        .into(),
+    );
+
+    // The recorded window does not exist, so the events are dropped
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    *app.world.resource_mut::<TimestampedInputs>() = recording.clone();
+    app.update();
+    let resized_events = app.world.resource::<Events<WindowResized>>();
+    assert_eq!(resized_events.iter_current_update_events().count(), 0);
+
+    // Once remapped, they are sent for the live window
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    let live_window = app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world);
+    app.insert_resource(PlaybackWindowRemap(
+        [(recorded_window, live_window)].into_iter().collect(),
+    ));
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+    app.update();
+
+    let resized_events = app.world.resource::<Events<WindowResized>>();
+    let resized: Vec<_> = resized_events.iter_current_update_events().collect();
+    assert_eq!(
+        resized,
+        [&WindowResized {
+            window: live_window,
+            width: 800.0,
+            height: 600.0,
+        }]
+    );
+    let focused_events = app.world.resource::<Events<WindowFocused>>();
+    let focused: Vec<_> = focused_events.iter_current_update_events().collect();
+    assert_eq!(
+        focused,
+        [&WindowFocused {
+            window: live_window,
+            focused: false,
+        }]
+    );
+
+    // The window itself is unchanged, so playback is not paused by the focus change
+    let window = app.world.get::<Window>(live_window).unwrap();
+    assert!(window.focused);
+}
+
+#[test]
+fn cursor_moved_outside_window_is_clamped() {
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    let window_entity = app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world);
+    let window = app.world.get::<Window>(window_entity).unwrap();
+    let bounds = Vec2::new(window.width(), window.height());
+
+    let mut recording = TimestampedInputs::default();
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        CursorMoved {
+            window: window_entity,
+            position: Vec2::new(-50.0, bounds.y + 50.0),
+        }
+        .into(),
+    );
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+    app.update();
+
+    let cursor_events = app.world.resource::<Events<CursorMoved>>();
+    let played: Vec<_> = cursor_events
+        .iter_current_update_events()
+        .map(|event| event.position)
+        .collect();
+    assert_eq!(played, [Vec2::new(0.0, bounds.y)]);
+    let window = app.world.get::<Window>(window_entity).unwrap();
+    assert_eq!(window.cursor_position(), Some(Vec2::new(0.0, bounds.y)));
+}
+
+#[test]
+fn clicks_play_back_at_recorded_cursor_position() {
+    let mut app = playback_app(PlaybackStrategy::FrameRangeOnce(
+        FrameCount(3),
+        FrameCount(4),
+    ));
+    let window_entity = app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world);
+    let position = Vec2::new(10.0, 20.0);
+
+    let mut recording = TimestampedInputs::default();
+    recording.send(
+        FrameCount(1),
+        Duration::ZERO,
+        CursorMoved {
+            window: window_entity,
+            position,
+        }
+        .into(),
+    );
+    recording.send(
+        FrameCount(3),
+        Duration::ZERO,
+        MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+        }
+        .into(),
+    );
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+
+    // The cursor movement is outside of the played range, but the click still lands where it was recorded
+    app.update();
+    let cursor_events = app.world.resource::<Events<CursorMoved>>();
+    let played: Vec<_> = cursor_events
+        .iter_current_update_events()
+        .map(|event| event.position)
+        .collect();
+    assert_eq!(played, [position]);
+    let window = app.world.get::<Window>(window_entity).unwrap();
+    assert_eq!(window.cursor_position(), Some(position));
+    let mouse_input = app.world.resource::<Input<MouseButton>>();
+    assert!(mouse_input.pressed(MouseButton::Left));
+}
+
+#[test]
+fn captured_clicks_play_back_at_same_frame_cursor_position() {
+    let mut capture_app = App::new();
+    capture_app
+        .add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(InputPlugin)
+        .add_plugin(InputCapturePlugin);
+    let window = capture_app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&capture_app.world);
+    let stale = Vec2::new(10.0, 20.0);
+    let clicked = Vec2::new(30.0, 40.0);
+
+    capture_app.world.send_event(CursorMoved {
+        window,
+        position: stale,
+    });
+    capture_app.update();
+    // The click is sent before the movement, but the movement is still captured first
+    capture_app.world.send_event(MouseButtonInput {
+        button: MouseButton::Left,
+        state: ButtonState::Pressed,
+    });
+    capture_app.world.send_event(CursorMoved {
+        window,
+        position: clicked,
+    });
+    capture_app.update();
+
+    let recording = capture_app.world.resource::<TimestampedInputs>().clone();
+    let click_frame = recording.events[2].frame;
+    assert!(matches!(
+        recording.events[1].input_event,
+        InputEvent::CursorMoved(_)
+    ));
+    assert!(matches!(
+        recording.events[2].input_event,
+        InputEvent::MouseButton(_)
+    ));
+
+    // Only the frame of the click is played back, so the stale movement is not
+    let mut app = playback_app(PlaybackStrategy::FrameRangeOnce(
+        click_frame,
+        click_frame + FrameCount(1),
+    ));
+    let live_window = app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world);
+    app.insert_resource(PlaybackWindowRemap(
+        [(window, live_window)].into_iter().collect(),
+    ));
+    *app.world.resource_mut::<TimestampedInputs>() = recording;
+    app.update();
+
+    // The recorded movement places the cursor, and is not sent a second time
+    let cursor_events = app.world.resource::<Events<CursorMoved>>();
+    let played: Vec<_> = cursor_events
+        .iter_current_update_events()
+        .map(|event| event.position)
+        .collect();
+    assert_eq!(played, [clicked]);
+    let window = app.world.get::<Window>(live_window).unwrap();
+    assert_eq!(window.cursor_position(), Some(clicked));
+    let mouse_input = app.world.resource::<Input<MouseButton>>();
+    assert!(mouse_input.pressed(MouseButton::Left));
+}
+
+#[test]
+fn scale_cursor_to_window_size() {
+    // Capture a click in a large window
+    let mut capture_app = App::new();
+    capture_app
+        .add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin {
+            primary_window: Some(Window {
+                resolution: WindowResolution::new(1920.0, 1080.0),
+                ..default()
+            }),
+            ..default()
+        })
+        .add_plugin(InputPlugin)
+        .add_plugin(InputCapturePlugin);
+    let capture_window = capture_app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&capture_app.world);
+    capture_app.world.send_event(CursorMoved {
+        window: capture_window,
+        position: Vec2::new(960.0, 270.0),
+    });
+    capture_app.world.send_event(MouseButtonInput {
+        button: MouseButton::Left,
+        state: ButtonState::Pressed,
+    });
+    capture_app.update();
+    let recording = capture_app.world.resource::<TimestampedInputs>().clone();
+    assert_eq!(
+        recording.metadata.resolution,
+        Some(Vec2::new(1920.0, 1080.0))
+    );
+
+    // Play it back to the default 1280x720 window
+    for (scale_cursor, expected) in [
+        (true, Vec2::new(640.0, 180.0)),
+        (false, Vec2::new(960.0, 270.0)),
+    ] {
+        let mut app = playback_app(PlaybackStrategy::FrameCount);
+        let live_window = app
+            .world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(&app.world);
+        app.insert_resource(PlaybackWindowRemap(
+            [(capture_window, live_window)].into_iter().collect(),
+        ))
+        .insert_resource(PlaybackScaleCursor(scale_cursor));
+        *app.world.resource_mut::<TimestampedInputs>() = recording.clone();
+        app.update();
+        app.update();
+
+        let window = app.world.get::<Window>(live_window).unwrap();
+        assert_eq!(window.cursor_position(), Some(expected));
+        assert!(app
+            .world
+            .resource::<Input<MouseButton>>()
+            .pressed(MouseButton::Left));
+    }
+}
+
+#[test]
+fn systems_ordered_after_playback_set() {
+    #[derive(Resource, Default)]
+    struct KeysRead(usize);
+
+    fn read_keys(mut keyboard_events: EventReader<KeyboardInput>, mut keys_read: ResMut<KeysRead>) {
+        keys_read.0 += keyboard_events.iter().count();
+    }
+
+    let mut app = playback_app(PlaybackStrategy::FrameCount);
+    app.init_resource::<KeysRead>()
+        .add_system(read_keys.after(PlaybackSet).in_base_set(CoreSet::First));
+    *app.world.resource_mut::<TimestampedInputs>() = simple_timestamped_input();
+
+    // Played back events are read on the same frame they are sent, even within `CoreSet::First`
+    app.update();
+    assert_eq!(app.world.resource::<KeysRead>().0, 1);
+}
+
+#[test]
+fn playback_jitter() {
+    let mut recording = TimestampedInputs::default();
+    for frame in 0..20 {
+        recording.send(
+            FrameCount(frame),
+            Duration::from_millis(16 * frame),
+            TEST_PRESS.into(),
+        );
+    }
+
+    // The number of events sent on each update
+    let sent_per_update = |strategy, frames, seed| {
+        let mut app = playback_app(strategy);
+        *app.world.resource_mut::<TimestampedInputs>() = recording.clone();
+        app.insert_resource(PlaybackRng::from_seed(seed))
+            .insert_resource(PlaybackJitter { frames });
+
+        let sent: Vec<_> = (0..30)
+            .map(|_| {
+                app.update();
+                let input_events = app.world.resource::<Events<KeyboardInput>>();
+                input_events.iter_current_update_events().count()
+            })
+            .collect();
+        // The recording itself is never changed, so it stays sorted by both frame and time
+        assert_eq!(
+            app.world.resource::<TimestampedInputs>().events,
+            recording.events
+        );
+        sent
+    };
+
+    let unjittered = sent_per_update(PlaybackStrategy::FrameCount, 0..=0, 7);
+    let jittered = sent_per_update(PlaybackStrategy::FrameCount, -2..=2, 7);
+    assert_ne!(jittered, unjittered);
+    assert_eq!(jittered.iter().sum::<usize>(), 20);
+    // The number of events sent over the first `n_updates`
+    let sent = |sent_per_update: &[usize], n_updates: usize| -> usize {
+        sent_per_update[..n_updates.min(30)].iter().sum()
+    };
+    for n_updates in 0..30 {
+        // Events are sent at most 2 updates early or late
+        assert!(sent(&jittered, n_updates) <= sent(&unjittered, n_updates + 2));
+        assert!(sent(&jittered, n_updates + 2) >= sent(&unjittered, n_updates));
+    }
+    // Some events are sent before their recorded frame, and some after it
+    assert!((0..30).any(|n_updates| sent(&jittered, n_updates) > sent(&unjittered, n_updates)));
+    assert!((0..30).any(|n_updates| sent(&jittered, n_updates) < sent(&unjittered, n_updates)));
+    // Only later shifts are possible when every shift is positive
+    let delayed = sent_per_update(PlaybackStrategy::FrameCount, 1..=2, 7);
+    for n_updates in 0..30 {
+        assert!(sent(&delayed, n_updates) <= sent(&unjittered, n_updates));
+    }
+    // The same seed always produces the same jitter
+    assert_eq!(
+        sent_per_update(PlaybackStrategy::FrameCount, -2..=2, 7),
+        jittered
+    );
+
+    // Delayed events are still sent once a range strategy has finished
+    let jittered_range = sent_per_update(
+        PlaybackStrategy::FrameRangeOnce(FrameCount(0), FrameCount(20)),
+        -2..=2,
+        7,
+    );
+    assert_eq!(jittered_range.iter().sum::<usize>(), 20);
+    let unjittered_range = sent_per_update(
+        PlaybackStrategy::FrameRangeOnce(FrameCount(0), FrameCount(20)),
+        0..=0,
+        7,
+    );
+    assert!((0..30)
+        .any(|n_updates| sent(&jittered_range, n_updates) > sent(&unjittered_range, n_updates)));
+    // Time-based strategies rely on the recording staying sorted by time
+    sent_per_update(PlaybackStrategy::Time, -2..=2, 7);
+}
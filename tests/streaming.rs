@@ -0,0 +1,97 @@
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+
+use bevy::window::WindowPlugin;
+use leafwing_input_playback::frame_counting::FrameCount;
+use leafwing_input_playback::input_capture::InputCapturePlugin;
+use leafwing_input_playback::input_playback::{InputPlaybackPlugin, PlaybackStrategy};
+use leafwing_input_playback::streaming::{
+    RecordingStreamReader, StreamingCapturePlugin, StreamingPlayback, StreamingPlaybackPlugin,
+};
+use leafwing_input_playback::timestamped_input::{InputEvent, TimestampedInputs};
+
+const TEST_PRESS: KeyboardInput = KeyboardInput {
+    scan_code: 1,
+    key_code: Some(KeyCode::F),
+    state: ButtonState::Pressed,
+};
+
+const TEST_RELEASE: KeyboardInput = KeyboardInput {
+    scan_code: 1,
+    key_code: Some(KeyCode::F),
+    state: ButtonState::Released,
+};
+
+#[test]
+fn stream_capture_and_playback() {
+    let path = std::env::temp_dir().join("leafwing_input_playback_stream.lipr");
+
+    let mut capture_app = App::new();
+    capture_app
+        .add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(InputPlugin)
+        .add_plugin(InputCapturePlugin)
+        .add_plugin(StreamingCapturePlugin {
+            path: path.clone(),
+            flush_interval: 1,
+        });
+
+    for _ in 0..5 {
+        capture_app.world.send_event(TEST_PRESS);
+        capture_app.world.send_event(TEST_RELEASE);
+        capture_app.update();
+        // Captured events are moved out of memory every frame
+        assert!(capture_app.world.resource::<TimestampedInputs>().is_empty());
+    }
+
+    let streamed: Vec<_> = RecordingStreamReader::open(&path)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(streamed.len(), 10);
+    assert_eq!(streamed[0].frame, FrameCount(1));
+    assert_eq!(streamed[9].frame, FrameCount(5));
+    assert_eq!(streamed[9].input_event, InputEvent::Keyboard(TEST_RELEASE));
+
+    let mut playback_app = App::new();
+    playback_app
+        .add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(InputPlugin)
+        .add_plugin(InputPlaybackPlugin)
+        .add_plugin(StreamingPlaybackPlugin {
+            path: path.clone(),
+            lookahead: 3,
+        })
+        .insert_resource(PlaybackStrategy::FrameCount);
+
+    let mut played = 0;
+    for _ in 0..6 {
+        playback_app.update();
+        let input_events = playback_app.world.resource::<Events<KeyboardInput>>();
+        played += input_events.iter_current_update_events().count();
+        // Only a few events are ever loaded at once
+        assert!(playback_app.world.resource::<TimestampedInputs>().len() <= 4);
+    }
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(played, 10);
+    assert!(playback_app
+        .world
+        .resource::<StreamingPlayback>()
+        .is_finished());
+}
+
+#[test]
+fn truncated_stream() {
+    let mut bytes = Vec::new();
+    assert!(RecordingStreamReader::new(&bytes[..]).next().is_none());
+
+    // A length prefix without its record
+    bytes.extend(10u32.to_le_bytes());
+    let mut reader = RecordingStreamReader::new(&bytes[..]);
+    assert!(reader.next().unwrap().is_err());
+}